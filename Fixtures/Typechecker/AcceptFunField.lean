@@ -0,0 +1,18 @@
+prelude
+set_option linter.all false -- prevent error messages from runFrontend
+
+inductive Nat where
+  | zero : Nat
+  | succ (n : Nat) : Nat
+
+inductive Box where
+  | mk (fn : Nat → Nat) : Box
+
+inductive Eq : α → α → Prop where
+  | refl (a : α) : Eq a a
+
+noncomputable def Box.run (b : Box) (n : Nat) : Nat :=
+  Box.rec (motive := fun _ => Nat) (fun fn => fn n) b
+
+theorem Box.run_mk : Eq (Box.run (Box.mk Nat.succ) Nat.zero) (Nat.succ Nat.zero) :=
+  Eq.refl _
@@ -0,0 +1,30 @@
+prelude
+set_option linter.all false -- prevent error messages from runFrontend
+
+inductive Nat where
+  | zero : Nat
+  | succ (n : Nat) : Nat
+
+inductive Vec (α : Type) : Nat → Type where
+  | nil : Vec α Nat.zero
+  | cons (n : Nat) (head : α) (tail : Vec α n) : Vec α (Nat.succ n)
+
+inductive Eq : α → α → Prop where
+  | refl (a : α) : Eq a a
+
+noncomputable def Vec.length {α : Type} {n : Nat} (v : Vec α n) : Nat :=
+  Vec.rec (motive := fun _ _ => Nat) Nat.zero (fun _ _ _ ih => Nat.succ ih) v
+
+def two : Nat := Nat.succ (Nat.succ Nat.zero)
+
+def emptyVec : Vec Nat Nat.zero := Vec.nil
+
+def singletonVec : Vec Nat (Nat.succ Nat.zero) := Vec.cons Nat.zero Nat.zero Vec.nil
+
+noncomputable def Vec.length_two : Nat :=
+  Vec.length (Vec.cons (Nat.succ Nat.zero) Nat.zero (Vec.cons Nat.zero Nat.zero Vec.nil))
+
+theorem Vec.length_cons :
+    Eq (Vec.length (Vec.cons (Nat.succ Nat.zero) Nat.zero (Vec.cons Nat.zero Nat.zero Vec.nil)))
+      (Nat.succ (Nat.succ Nat.zero)) :=
+  Eq.refl _
@@ -0,0 +1,25 @@
+prelude
+set_option linter.all false -- prevent error messages from runFrontend
+
+inductive Nat where
+  | zero : Nat
+  | succ (n : Nat) : Nat
+
+inductive List (α : Type) where
+  | nil : List α
+  | cons (head : α) (tail : List α) : List α
+
+inductive Tree where
+  | node (children : List Tree) : Tree
+
+inductive Eq : α → α → Prop where
+  | refl (a : α) : Eq a a
+
+noncomputable def Tree.leftDepth (t : Tree) : Nat :=
+  Tree.rec (motive_1 := fun _ => Nat) (motive_2 := fun _ => Nat)
+    (fun _ ih => Nat.succ ih) Nat.zero (fun _ _ ih _ => ih) t
+
+theorem Tree.leftDepth_two :
+    Eq (Tree.leftDepth (Tree.node (List.cons (Tree.node List.nil) List.nil)))
+      (Nat.succ (Nat.succ Nat.zero)) :=
+  Eq.refl _
@@ -0,0 +1,16 @@
+prelude
+set_option linter.all false -- prevent error messages from runFrontend
+
+inductive Nat where
+  | zero : Nat
+  | succ (n : Nat) : Nat
+
+inductive Eq : α → α → Prop where
+  | refl (a : α) : Eq a a
+
+init_quot
+
+theorem Quot.lift_mk (r : Nat → Nat → Prop) (f : Nat → Nat)
+    (h : ∀ a b, r a b → Eq (f a) (f b)) :
+    Eq (Quot.lift f h (Quot.mk r Nat.zero)) (f Nat.zero) :=
+  Eq.refl _
@@ -0,0 +1,19 @@
+prelude
+set_option linter.all false -- prevent error messages from runFrontend
+
+inductive Nat where
+  | zero : Nat
+  | succ (n : Nat) : Nat
+
+structure Pair where
+  fst : Nat
+  snd : Nat → Nat
+
+inductive Eq : α → α → Prop where
+  | refl (a : α) : Eq a a
+
+theorem Pair.snd_mk : Eq ((Pair.mk Nat.zero Nat.succ).snd Nat.zero) (Nat.succ Nat.zero) :=
+  Eq.refl _
+
+theorem Pair.fst_neutral (p : Pair) : Eq (Pair.mk p.fst p.snd).fst p.fst :=
+  Eq.refl _
@@ -0,0 +1,19 @@
+import LSpec
+import Yatima.Cli.TypecheckCmd
+
+open LSpec Lean (Json)
+
+def field (json : Json) (key : String) : Option Json :=
+  (json.getObjVal? key).toOption
+
+def jsonTests : TestSeq :=
+  let ok := typecheckResultToJson "A.lean" (.ok ())
+  let error := typecheckResultToJson "A.lean" (.error "Type mismatch")
+  test "Successes have the source" (field ok "source" == some (.str "A.lean")) ++
+  test "Successes are ok" (field ok "ok" == some (.bool true)) ++
+  test "Successes have no error" (field ok "error").isNone ++
+  test "Failures have the source" (field error "source" == some (.str "A.lean")) ++
+  test "Failures aren't ok" (field error "ok" == some (.bool false)) ++
+  test "Failures have the error" (field error "error" == some (.str "Type mismatch"))
+
+def main := lspecIO jsonTests
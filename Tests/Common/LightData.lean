@@ -0,0 +1,41 @@
+import LSpec
+import Yatima.Common.LightData
+
+open LSpec Yatima IR ContAddr
+
+/-- Replaces the format version that prefixes encoded data with `version` -/
+def withVersion (version : Nat) : LightData → LightData
+  | .cell data => .cell $ data.set! 0 version
+  | x => x
+
+def const : Const := .axiom ⟨0, .sort .zero⟩
+
+def decodeEnv (x : LightData) : Except String Unit :=
+  discard (dec x : Except String Env)
+
+def decodeConst (x : LightData) : Except String Unit :=
+  discard (dec x : Except String Const)
+
+def rejectsVersion (descr : String) (exc : Except String Unit) : TestSeq :=
+  withExceptError descr exc fun e =>
+    test "The error mentions the format version"
+      ((e.splitOn "Unsupported").length > 1 && (e.splitOn "format version").length > 1)
+
+def versionTests : TestSeq :=
+  withExceptOk "Environments round-trip" (decodeEnv $ Encodable.encode (default : Env))
+    (fun _ => .done) ++
+  withExceptOk "Constants round-trip" (decodeConst $ Encodable.encode const)
+    (fun _ => .done) ++
+  rejectsVersion "Environments from a future format are rejected"
+    (decodeEnv $ withVersion (envFormatVersion + 1) $ Encodable.encode (default : Env)) ++
+  rejectsVersion "Constants from a future format are rejected"
+    (decodeConst $ withVersion (constFormatVersion + 1) $ Encodable.encode const)
+
+def encodedSizeTests : TestSeq :=
+  let data : LightData := Encodable.encode const
+  test "The encoded size of a constant is the size of its bytes"
+    (encodedSize const == data.toByteArray.size) ++
+  test "The encoded size of an environment is the size of its bytes"
+    (encodedSize (default : Env) == (Encodable.encode (default : Env) : LightData).toByteArray.size)
+
+def main := lspecIO $ versionTests ++ encodedSizeTests
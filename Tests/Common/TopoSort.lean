@@ -0,0 +1,33 @@
+import LSpec
+import Yatima.Common.TopoSort
+
+open LSpec Yatima
+
+/-- `3` depends on `1` and `2`, which both depend on `0` -/
+def diamond : Nat → List Nat
+  | 3 => [1, 2]
+  | 1 | 2 => [0]
+  | _ => []
+
+/-- `0` and `1` depend on each other -/
+def cycle : Nat → List Nat
+  | 0 => [1]
+  | 1 => [0]
+  | _ => []
+
+def comesBefore (order : Array Nat) (x y : Nat) : Bool :=
+  match order.indexOf? x, order.indexOf? y with
+  | some i, some j => i < j
+  | _, _ => false
+
+def topoSortTests : TestSeq :=
+  withExceptOk "Sorting the diamond succeeds" (topoSort [3] diamond) (fun order =>
+    test "Every node is sorted once" (order.size == 4) $
+    test "Dependencies come first" ([(0, 1), (0, 2), (1, 3), (2, 3)].all fun (x, y) =>
+      comesBefore order x y)) ++
+  withExceptOk "Sorting shared roots succeeds" (topoSort [1, 2] diamond) (fun order =>
+    test "Shared dependencies are sorted once" (order == #[0, 1, 2])) ++
+  withExceptError "Sorting a cycle fails" (topoSort [0] cycle) (fun e =>
+    test "The error mentions the cycle" ((e.splitOn "Dependency cycle").length > 1))
+
+def main := lspecIO topoSortTests
@@ -0,0 +1,15 @@
+import LSpec
+import Yatima.ContAddr.ContAddrError
+
+open LSpec Yatima.ContAddr
+
+def err : ContAddrError := .constantNotContentAddressed `foo
+
+def liftTests : IO TestSeq := do
+  let ok ← (monadLift (Except.ok 1 : Except ContAddrError Nat) : IO Nat).toBaseIO
+  let error ← (monadLift (Except.error err : Except ContAddrError Nat) : IO Nat).toBaseIO
+  return test "Values are lifted into IO" (match ok with | .ok 1 => true | _ => false) ++
+    test "Errors are thrown with their message"
+      (match error with | .error e => toString e == toString err | .ok _ => false)
+
+def main := do lspecIO (← liftTests)
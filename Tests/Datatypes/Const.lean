@@ -0,0 +1,63 @@
+import LSpec
+import Yatima.Datatypes.Const
+
+open LSpec Yatima IR
+
+def polyAxiom : Const :=
+  .axiom ⟨2, .pi (.sort (.var 1)) (.const (.ofNat 0) [.var 0, .succ (.var 1)])⟩
+
+def instantiateUnivsTests : TestSeq :=
+  withExceptOk "Instantiating the universes of an axiom succeeds"
+    (polyAxiom.instantiateUnivs [.zero, .succ .zero]) (fun const =>
+      test "The universes are replaced" (const == .axiom ⟨0,
+        .pi (.sort (.succ .zero)) (.const (.ofNat 0) [.zero, .succ (.succ .zero)])⟩)) ++
+  withExceptError "Instantiating with too few universes fails"
+    (polyAxiom.instantiateUnivs [.zero]) (fun _ => .done) ++
+  withExceptError "Instantiating the universes of a projection fails"
+    ((Const.inductiveProj ⟨.ofNat 0, 0⟩).instantiateUnivs []) (fun _ => .done)
+
+def mkInductive (type : Expr) : Inductive :=
+  ⟨0, type, 0, 0, [], [], false, false, false, false⟩
+
+def inductiveSortTests : TestSeq :=
+  test "The sort of an inductive is read after its binders"
+    ((mkInductive (.pi (.sort .zero) (.sort (.succ .zero)))).resultSort? == some (.succ .zero)) ++
+  test "Types that don't end in a sort have no sort"
+    ((mkInductive (.pi (.sort .zero) (.var 0 []))).resultSort? == none) ++
+  test "Inductives in Prop are propositions"
+    (mkInductive (.pi (.sort (.succ .zero)) (.sort .zero))).isProp ++
+  test "Inductives in Type aren't propositions" (!(mkInductive (.sort (.succ .zero))).isProp) ++
+  test "Inductives in Sort u aren't propositions" (!(mkInductive (.sort (.var 0))).isProp)
+
+/-- A constructor with a parameter `α : Type` and the given number of fields -/
+def pairCtor (fields : Nat) : Constructor :=
+  ⟨0, .pi (.sort (.succ .zero)) $ .pi (.var 0 []) $ .pi (.var 1 []) (.var 3 []), 0, 1, fields⟩
+
+def fieldTypesTests : TestSeq :=
+  test "The types of two fields come after the parameter"
+    ((pairCtor 2).fieldTypes == some [.var 0 [], .var 1 []]) ++
+  test "Types with too few binders have no field types" ((pairCtor 3).fieldTypes == none)
+
+def typeTests : TestSeq :=
+  let type : Expr := .sort .zero
+  let value : Expr := .var 0 []
+  let typed : List (String × Const) := [
+    ("axioms", .axiom ⟨0, type⟩),
+    ("theorems", .theorem ⟨0, type, value⟩),
+    ("opaques", .opaque ⟨0, type, value⟩),
+    ("definitions", .definition ⟨0, type, value, false⟩),
+    ("quotients", .quotient ⟨0, type, .type⟩)]
+  let untyped : List (String × Const) := [
+    ("inductive projections", .inductiveProj ⟨.ofNat 0, 0⟩),
+    ("constructor projections", .constructorProj ⟨.ofNat 0, 0, 0⟩),
+    ("recursor projections", .recursorProj ⟨.ofNat 0, 0, 0⟩),
+    ("definition projections", .definitionProj ⟨.ofNat 0, 0⟩),
+    ("definition blocks", .mutDefBlock []),
+    ("inductive blocks", .mutIndBlock [])]
+  let tSeq := typed.foldl (init := .done) fun tSeq (descr, const) =>
+    tSeq ++ test s!"The type of {descr} is found" (const.type? == some type)
+  untyped.foldl (init := tSeq) fun tSeq (descr, const) =>
+    tSeq ++ test s!"The type of {descr} isn't found" (const.type? == none)
+
+def main := lspecIO $
+  instantiateUnivsTests ++ inductiveSortTests ++ fieldTypesTests ++ typeTests
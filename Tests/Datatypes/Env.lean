@@ -0,0 +1,17 @@
+import LSpec
+import Yatima.Datatypes.Env
+
+open LSpec Yatima IR
+
+def env : Env :=
+  (default : Env).insertConst `a (.ofNat 1) |>.insertConst `b (.ofNat 2)
+
+def renameTests : TestSeq :=
+  withExceptOk "Renaming a to c succeeds" (env.rename `a `c) (fun env' =>
+    test "c has the hash of a" (env'.getConst? `c == some (.ofNat 1)) $
+    test "a is gone" (env'.getConst? `a).isNone $
+    test "b is untouched" (env'.getConst? `b == some (.ofNat 2))) ++
+  withExceptError "Renaming a to b fails" (env.rename `a `b) (fun _ => .done) ++
+  withExceptError "Renaming a missing constant fails" (env.rename `d `e) (fun _ => .done)
+
+def main := lspecIO renameTests
@@ -0,0 +1,60 @@
+import LSpec
+import Yatima.Common.ToDot
+
+open LSpec Yatima IR
+
+def ty : Expr := .sort .zero
+def lit (n : Nat) : Expr := .lit (.natVal n)
+def var (idx : Nat) : Expr := .var idx []
+
+def instantiate1Tests : TestSeq :=
+  test "The replaced variable becomes the value"
+    ((var 0).instantiate1 (lit 1) == lit 1) $
+  test "Variables above the replaced one are decremented"
+    ((var 2).instantiate1 (lit 1) == var 1) $
+  test "Loose variables of the value are lifted under binders"
+    ((Expr.lam ty (.app (var 0) (var 1))).instantiate1 (var 0) ==
+      .lam ty (.app (var 0) (var 1))) $
+  test "Bound variables of the value aren't lifted"
+    ((Expr.lam ty (var 1)).instantiate1 (.lam ty (.app (var 0) (var 1))) ==
+      .lam ty (.lam ty (.app (var 0) (var 2))))
+
+def betaHeadTests : TestSeq :=
+  test "A redex is contracted"
+    ((Expr.app (.lam ty (.app (var 0) (var 1))) (lit 1)).betaHead? ==
+      some (.app (lit 1) (var 0))) $
+  test "Other expressions aren't contracted"
+    ((Expr.app (var 0) (lit 1)).betaHead? == none)
+
+def body : Expr := .app (.app (var 0) (var 1)) (var 2)
+
+def instantiateTests : TestSeq :=
+  test "Values replace their variables and the ones above are decremented"
+    (body.instantiate [lit 0, lit 1] == .app (.app (lit 0) (lit 1)) (var 0)) $
+  test "Loose variables of the values are lifted under binders"
+    ((Expr.lam ty (.app (var 1) (var 2))).instantiate [var 0, var 5] ==
+      .lam ty (.app (var 1) (var 6))) $
+  test "Agrees with instantiate1 on closed values"
+    (body.instantiate [lit 0, lit 1] == (body.instantiate1 (lit 0)).instantiate1 (lit 1)) $
+  test "Agrees with instantiate1 on lifted loose values"
+    (body.instantiate [var 3, var 4] ==
+      (body.instantiate1 ((var 3).liftLooseVars 1)).instantiate1 (var 4))
+
+def shared : Expr := .lam ty (var 0)
+
+/-- The number of nodes labeled `label` in a DOT graph -/
+def countNodes (dot label : String) : Nat :=
+  (dot.splitOn s!"[label={label.quote}];").length - 1
+
+def toDotTests : TestSeq :=
+  let dot := (Expr.app (.app shared shared) shared).toDot
+  test "A shared subterm is emitted once" (countNodes dot "lam" == 1) $
+  test "Distinct subterms are emitted separately" (countNodes dot "app" == 2) $
+  test "Every occurrence of a shared subterm gets an edge"
+    ((dot.splitOn " -> n").length - 1 == 6)
+
+def main := lspecIO $
+  group "instantiate1" instantiate1Tests ++
+  group "instantiate" instantiateTests ++
+  group "betaHead?" betaHeadTests ++
+  group "toDot" toDotTests
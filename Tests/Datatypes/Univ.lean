@@ -0,0 +1,13 @@
+import LSpec
+import Yatima.Datatypes.Univ
+
+open LSpec Yatima IR
+
+def reduceMaxTests : TestSeq :=
+  test "max u u reduces to u" (Univ.reduceMax (.var 0) (.var 0) == .var 0) ++
+  test "max (u + 1) (u + 1) reduces to u + 1"
+    (Univ.reduceMax (.succ (.var 0)) (.succ (.var 0)) == .succ (.var 0)) ++
+  test "max 0 u reduces to u" (Univ.reduceMax .zero (.var 0) == .var 0) ++
+  test "max u v doesn't reduce" (Univ.reduceMax (.var 0) (.var 1) == .max (.var 0) (.var 1))
+
+def main := lspecIO reduceMaxTests
@@ -5,8 +5,26 @@ def tcFixturesPath : System.FilePath :=
 
 open LSpec in
 def main := do
-  lspecIO $ ← ensembleTestExtractors'
+  let tests ← ensembleTestExtractors'
     [ /-tcFixturesPath / "AcceptMutual.lean",-/
-      tcFixturesPath / "AcceptFunApp.lean" ]
-    [extractTypecheckingTests]
+      tcFixturesPath / "AcceptFunApp.lean",
+      tcFixturesPath / "AcceptFunField.lean",
+      tcFixturesPath / "AcceptIndexedFamily.lean",
+      tcFixturesPath / "AcceptNested.lean",
+      tcFixturesPath / "AcceptQuot.lean",
+      tcFixturesPath / "AcceptStructProj.lean" ]
+    [ extractTypecheckingTests, extractStoreVerificationTests, extractNameResolutionTests,
+      extractPrintingOrderTests ]
     []
+  let indexedFamilyTests ← ensembleTestExtractors (tcFixturesPath / "AcceptIndexedFamily.lean")
+    [ extractDefEqTests [(`Vec.length_two, `two)],
+      extractNotDefEqTests [(`emptyVec, `singletonVec,
+        ["Reduced emptyVec: Vec.nil Nat", "Reduced singletonVec: Vec.cons Nat ", "(Vec.nil Nat)"])],
+      extractAxiomsUsedTests `Vec.length_cons [],
+      extractUsesSorryTests `Vec.length_cons false,
+      extractIncrementalTests `Vec.length [`Vec.length, `Vec.length_cons, `Vec.length_two],
+      extractCheckOnceTests `two ]
+    []
+  let quotTests ← ensembleTestExtractors (tcFixturesPath / "AcceptQuot.lean")
+    [extractQuotTypeTests] []
+  lspecIO $ tests ++ indexedFamilyTests ++ quotTests
@@ -0,0 +1,23 @@
+import LSpec
+import Yatima.Typechecker.Datatypes
+
+open LSpec Yatima Typechecker
+
+def prop : TypedExpr := ⟨.none, .sort .zero⟩
+
+def majorIdxTests : TestSeq :=
+  test "The major premise of a recursor comes after its other arguments"
+    ((TypedConst.recursor prop 1 2 3 4 false true default #[]).majorIdx? == some 10) ++
+  test "Constructors have no major premise"
+    ((TypedConst.constructor prop 0 1 2).majorIdx? == none)
+
+def unfoldTests : TestSeq :=
+  let value : TypedExpr := ⟨.none, .var 0⟩
+  test "Theorems unfold" ((TypedConst.theorem prop value).unfold? == some value) ++
+  test "Definitions unfold" ((TypedConst.definition prop value false).unfold? == some value) ++
+  test "Partial definitions don't unfold"
+    ((TypedConst.definition prop value true).unfold? == none) ++
+  test "Opaque constants don't unfold" ((TypedConst.opaque prop value).unfold? == none) ++
+  test "Axioms don't unfold" ((TypedConst.axiom prop).unfold? == none)
+
+def main := lspecIO $ majorIdxTests ++ unfoldTests
@@ -0,0 +1,21 @@
+import LSpec
+import Yatima.Typechecker.Equal
+
+open LSpec Yatima Typechecker
+
+/-- `fv_0` applied to itself `depth` times -/
+def nested : Nat → Value
+  | 0 => .app (.fvar 0) [] []
+  | depth + 1 => .app (.fvar 0) [⟨.none, .pure (nested depth)⟩] [.none]
+
+def equalWithFuel (fuel : Nat) (val : Value) : Except String Bool :=
+  TypecheckM.run { (default : TypecheckCtx) with equalFuel := fuel } default $
+    equal 0 ⟨.none, .pure val⟩ ⟨.none, .pure val⟩
+
+def fuelTests : TestSeq :=
+  withExceptOk "Shallow values are compared within the fuel" (equalWithFuel 10 (nested 3))
+    (fun eq => test "They are equal" eq) ++
+  withExceptError "Deep values run out of fuel" (equalWithFuel 2 (nested 3)) (fun e =>
+    test "The error mentions the depth limit" ((e.splitOn "depth limit").length > 1))
+
+def main := lspecIO fuelTests
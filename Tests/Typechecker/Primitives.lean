@@ -0,0 +1,21 @@
+import LSpec
+import Yatima.Typechecker.TypecheckM
+
+open LSpec Yatima Typechecker
+
+def strLit (s : String) : SusValue := ⟨.none, .pure (.lit (.strVal s))⟩
+
+def runOp (op : PrimConstOp) (args : Array SusValue) : Except String (Option Value) :=
+  TypecheckM.run default default (op.toPrimOp.op args)
+
+def isLit (lit : Lean.Literal) : Option Value → Bool
+  | some (.lit lit') => lit == lit'
+  | _ => false
+
+def strPrimTests : TestSeq :=
+  withExceptOk "String.append runs" (runOp .strAppend #[strLit "a", strLit "b"]) (fun v =>
+    test "String.append reduces literals" (isLit (.strVal "ab") v)) ++
+  withExceptOk "String.length runs" (runOp .strLength #[strLit "ab"]) (fun v =>
+    test "String.length reduces literals" (isLit (.natVal 2) v))
+
+def main := lspecIO strPrimTests
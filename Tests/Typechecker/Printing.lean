@@ -0,0 +1,70 @@
+import LSpec
+import Yatima.Typechecker.Printing
+
+open LSpec Yatima Typechecker PP
+
+/-- A value whose evaluation fails, as that of an ill-typed term would -/
+def failing : SusValue := ⟨.none, .mk fun _ => .exception "evaluation failed"⟩
+
+def ppCtxWith (types : List SusValue) (force : Bool) : Except String String :=
+  TypecheckM.run { (default : TypecheckCtx) with lvl := types.length, types } default do
+    return toString (← ppTypecheckCtx force)
+
+def mentions (s part : String) : Bool :=
+  (s.splitOn part).length > 1
+
+def ppTypecheckCtxTests : TestSeq :=
+  withExceptOk "Printing a context without forcing succeeds" (ppCtxWith [failing] false)
+    (fun s =>
+      test "The value is shown as suspended" (mentions s "<suspended none>") $
+      test "The value isn't evaluated" (!mentions s "evaluation failed")) ++
+  withExceptOk "Printing a context with forcing succeeds" (ppCtxWith [failing] true)
+    (fun s =>
+      test "A failing value is shown as suspended" (mentions s "<suspended none>") $
+      test "Its failure isn't shown" (!mentions s "evaluation failed"))
+
+def ppUnivsTests : TestSeq :=
+  test "Universe arguments are printed as .{u, v}"
+    (toString (IR.PP.ppUnivs [.var 0, .succ (.var 1)]) == ".{_#0, 1+_#1}") ++
+  test "No universe arguments print nothing" (toString (IR.PP.ppUnivs []) == "")
+
+def names : ConstNames :=
+  Std.RBMap.empty |>.insert (.ofNat 1) `f |>.insert (.ofNat 2) `g
+
+def pp (x : TypecheckM Std.Format) : String :=
+  match TypecheckM.run { (default : TypecheckCtx) with constNames := names } default x with
+  | .ok fmt => toString fmt
+  | .error e => e
+
+def f : IR.Expr := .const (.ofNat 1) []
+def g : IR.Expr := .const (.ofNat 2) []
+def x : IR.Expr := .var 0 []
+
+/-- `f` applied to `args`, given in order, as a stuck value -/
+def fValue (args : List Value) : Value :=
+  .app (.const (.ofNat 1) []) (args.reverse.map fun arg => ⟨.none, .pure arg⟩) []
+
+def fvar (idx : Nat) : Value := .app (.fvar idx) [] []
+
+def parenTests : TestSeq :=
+  test "Nested arguments are parenthesized"
+    (pp (IR.PP.ppExpr (.app f (.app g x))) == "f (g v_0)") ++
+  test "Nested functions aren't parenthesized"
+    (pp (IR.PP.ppExpr (.app (.app f g) x)) == "f g v_0") ++
+  test "Applied lambdas are parenthesized"
+    (let s := pp (IR.PP.ppExpr (.app (.lam (.sort .zero) x) f))
+      s.startsWith "(fun (_ : Prop) =>" && s.endsWith "v_0) f") ++
+  test "Projections of applications are parenthesized"
+    (pp (IR.PP.ppExpr (.proj 0 (.app f x))) == "(f v_0).0") ++
+  test "Projections of atoms aren't parenthesized" (pp (IR.PP.ppExpr (.proj 0 x)) == "v_0.0") ++
+  test "Nested argument values are parenthesized"
+    (pp (ppValue (fValue [fValue [fvar 0], fvar 1])) == "f (f fv_0) fv_1") ++
+  test "Sort argument values are parenthesized"
+    (pp (ppValue (fValue [.sort (.succ .zero), .sort .zero])) == "f (Sort 1) Prop") ++
+  test "Projections of stuck values are parenthesized"
+    (pp (ppValue (.app (.proj (.ofNat 1) 0 ⟨.none, fValue [fvar 0]⟩) [] [])) == "(f fv_0).0")
+
+def main := lspecIO $
+  group "ppTypecheckCtx" ppTypecheckCtxTests ++
+  group "ppUnivs" ppUnivsTests ++
+  group "Parentheses" parenTests
@@ -3,12 +3,84 @@ import TestsUtils.ContAddrAndExtractTests
 def tcFixturesPath : System.FilePath :=
   "Fixtures" / "Typechecker"
 
+open Yatima IR ContAddr Typechecker in
+/--
+A block of two mutual partial definitions of `Type` at universe `u`, the first
+of which refers to the second at universe `u + 1`. Lean doesn't elaborate such
+definitions, so the store is built by hand
+-/
+def univRecursionStore : Store :=
+  let defn (value : Expr) : Definition := ⟨1, .sort (.succ .zero), value, true⟩
+  let block : Const := .mutDefBlock
+    [defn (.var 1 [.succ (.var 0)]), defn (.var 0 [.var 0])]
+  let blockF := hashConst block true default |>.1
+  let store : Store := Std.RBMap.empty.insert blockF block
+  [0, 1].foldl (init := store) fun store idx =>
+    store.insert (mkDefinitionProjF blockF idx true) (.definitionProj ⟨blockF, idx⟩)
+
+open Yatima IR in
+/-- `fun A B a => a : (A B : Prop) → A → B`, whose value doesn't have its type -/
+def mismatchStore : Yatima.Typechecker.Store :=
+  let prop : Expr := .sort .zero
+  mkQuickStore [.definition ⟨0,
+    .pi prop (.pi prop (.pi (.var 1 []) (.var 1 []))),
+    .lam prop (.lam prop (.lam (.var 1 []) (.var 0 []))), false⟩]
+
+open Yatima IR ContAddr Typechecker in
+/--
+A store with the projections of a block made of a single inductive, which is
+built by hand to describe recursors that Lean wouldn't generate
+-/
+def indBlockStore (ind : Inductive) : Store :=
+  let block : Const := .mutIndBlock [ind]
+  let blockF := hashConst block true default |>.1
+  let store : Store := Std.RBMap.empty
+    |>.insert blockF block
+    |>.insert (mkInductiveProjF blockF 0 true) (.inductiveProj ⟨blockF, 0⟩)
+  let store := ind.ctors.enum.foldl (init := store) fun store (cidx, _) =>
+    store.insert (mkConstructorProjF blockF 0 cidx true) (.constructorProj ⟨blockF, 0, cidx⟩)
+  ind.recrs.enum.foldl (init := store) fun store (ridx, _) =>
+    store.insert (mkRecursorProjF blockF 0 ridx true) (.recursorProj ⟨blockF, 0, ridx⟩)
+
+open Yatima IR in
+/--
+A proposition with two constructors `t` and `f`, whose recursor eliminates into
+`Sort u` and has the given rules
+-/
+def twoCtorProp (rules : List RecursorRule) : Inductive :=
+  let prop : Expr := .sort .zero
+  let ctor (idx : Nat) : Constructor := ⟨0, .var 0 [], idx, 0, 0⟩
+  -- `(motive : P → Sort u) → motive t → motive f → (b : P) → motive b`
+  let recrType : Expr := .pi (.pi (.var 0 []) (.sort (.var 0))) $
+    .pi (.app (.var 0 []) (.var 2 [])) $ .pi (.app (.var 1 []) (.var 4 [])) $
+      .pi (.var 3 []) (.app (.var 3 []) (.var 0 []))
+  let recr : Recursor := ⟨1, recrType, 0, 0, 1, 2, rules, false, true⟩
+  ⟨0, prop, 0, 0, [ctor 0, ctor 1], [recr], false, false, false, false⟩
+
 open LSpec in
 def main := do
-  lspecIO $ ← ensembleTestExtractors'
+  let tests ← ensembleTestExtractors'
     [ --tcFixturesPath / "RejectInfListFalse.lean",
       tcFixturesPath / "RejectMetaFalse.lean",
       tcFixturesPath / "RejectAxiomFalse.lean",
       tcFixturesPath / "RejectSorry.lean" ]
     [extractNonTypecheckingTests]
     []
+  let axiomsTests ← ensembleTestExtractors (tcFixturesPath / "RejectAxiomFalse.lean")
+    [extractAxiomsUsedTests `False.intro [`FalseIntro], extractTypecheckChangedTests] []
+  let sorryTests ← ensembleTestExtractors (tcFixturesPath / "RejectSorry.lean")
+    [extractUsesSorryTests `False.intro true] []
+  lspecIO $ tests ++ axiomsTests ++ sorryTests ++
+    withExceptErrorContaining "Universe polymorphic recursion is rejected"
+      (Yatima.Typechecker.typecheckAll univRecursionStore default)
+      ["Universe polymorphic recursion"] ++
+    withExceptErrorContaining "Type mismatches are rejected"
+      (Yatima.Typechecker.typecheckAll mismatchStore default)
+      ["Type mismatch", "Expected type: ", "Found type: ", "typecheckCtx with"] ++
+    withExceptErrorContaining "Large elimination of non-subsingletons is rejected"
+      (Yatima.Typechecker.typecheckAll (indBlockStore $ twoCtorProp
+        [⟨0, .var 1 []⟩, ⟨0, .var 0 []⟩]) default)
+      ["eliminates a proposition that isn't a subsingleton into any sort"] ++
+    withExceptErrorContaining "Recursors with a rule missing are rejected"
+      (Yatima.Typechecker.typecheckAll (indBlockStore $ twoCtorProp [⟨0, .var 1 []⟩]) default)
+      ["has 1 rules, but its inductive has 2 constructors"]
@@ -15,6 +15,17 @@ def withExceptOkM [Monad m] (descr : String) (exc : Except ε α) [ToString ε]
   | .error e => return test descr (ExpectationFailure "ok _" s!"error {e}")
   | .ok    a => return test descr true $ ← f a
 
+/-- Asserts that `exc` fails with a message that contains each string of `parts` -/
+def withExceptErrorContaining (descr : String) (exc : Except String α) [ToString α]
+    (parts : List String) : TestSeq :=
+  withExceptError descr exc fun e => parts.foldl (init := .done) fun tSeq part =>
+    tSeq ++ test s!"The error mentions {part.quote}" ((e.splitOn part).length > 1)
+
+/-- A store holding `consts` under their quick hashes -/
+def mkQuickStore (consts : List Const) : Store :=
+  consts.foldl (init := .empty) fun store const =>
+    store.insert (hashConst const true default).1 const
+
 abbrev Extractor := ContAddrState → TestSeq
 abbrev IOExtractor := ContAddrState → IO TestSeq
 
@@ -51,6 +62,148 @@ def extractNonTypecheckingTests : Extractor := fun stt =>
   withExceptError "Typechecking fails" (typecheckAll stt.store stt.env.constNames)
     fun _ => .done
 
+/--
+Asserts that `typecheckChanged` checks nothing when every constant is in the
+previous store, and fails like `typecheckAll` when none is. Meant for stores
+that don't typecheck
+-/
+def extractTypecheckChangedTests : Extractor := fun stt =>
+  let names := stt.env.constNames
+  withExceptOk "Checking against the same store succeeds"
+      (typecheckChanged stt.store stt.store names) fun _ =>
+    withExceptError "Checking against an empty store fails"
+      (typecheckChanged stt.store default names) fun _ => .done
+
+/--
+Asserts that `typecheckChanged` checks exactly the constants named `expected`,
+and unnamed ones such as blocks, when the previous store holds every constant
+that doesn't depend on `changed`, as if `changed` had just been edited
+-/
+def extractIncrementalTests (changed : Name) (expected : List Name) : Extractor := fun stt =>
+  withOptionSome s!"{changed} is found" (stt.env.getConst? changed) fun f =>
+    let previous : Store := stt.store.foldl (init := .empty) fun acc hash const =>
+      if (reachable stt.store hash).contains f then acc else acc.insert hash const
+    let names := stt.env.constNames
+    withExceptOk "Checking the changed constants succeeds"
+        (typecheckChanged stt.store previous names) fun checked =>
+      let checked := checked.toList.filterMap names.find?
+      test s!"Only {expected} are checked"
+        (checked.length == expected.length && expected.all checked.contains)
+
+/--
+Asserts that checking the constant `name` again keeps its typed version instead
+of checking it anew, by replacing that version with a marker in between
+-/
+def extractCheckOnceTests (name : Name) : Extractor := fun stt =>
+  withOptionSome s!"{name} is found" (stt.env.getConst? name) fun f =>
+    let marker : TypedConst := .axiom ⟨.none, .sort .zero⟩
+    let aux := do
+      checkConst f
+      modify fun stt => { stt with typedConsts := stt.typedConsts.insert f marker }
+      checkConst f
+      pure $ (← get).typedConsts.find? f == some marker
+    withExceptOk s!"Checking {name} twice succeeds"
+        (TypecheckM.run (.init stt.store stt.env.constNames true) default aux) fun kept =>
+      test s!"The second check of {name} does no work" kept
+
+/--
+Asserts that every hash in the store is the one of the constant it maps to, and
+that swapping the constant of a hash for another one gets exactly that hash reported
+-/
+def extractStoreVerificationTests (quick : Bool := true) : Extractor := fun stt =>
+  test "All hashes in the store match their constants" (verifyStore stt.store quick).isEmpty ++
+  match stt.store.toList with
+  | (f, _) :: (_, const) :: _ =>
+    test "Only the hash of a swapped constant is reported"
+      (verifyStore (stt.store.insert f const) quick == #[f])
+  | _ => .done
+
+/--
+Asserts that every name of the environment resolves to a constant in the store,
+and that erasing the constant of a name gets exactly the names of its hash reported
+-/
+def extractNameResolutionTests : Extractor := fun stt =>
+  test "All names resolve in the store" (stt.env.danglingNames stt.store).isEmpty ++
+  match stt.env.constsList with
+  | (_, f) :: _ =>
+    let expected := stt.env.foldConsts #[] fun acc name hash =>
+      if hash == f then acc.push name else acc
+    test "Only the names of an erased constant are dangling"
+      (stt.env.danglingNames (stt.store.erase f) == expected)
+  | [] => .done
+
+/-- Asserts that the axioms the constant `root` relies on are exactly `expected` -/
+def extractAxiomsUsedTests (root : Name) (expected : List Name) : Extractor := fun stt =>
+  withOptionSome s!"{root} is found" (stt.env.getConst? root) fun hash =>
+    let axioms := stt.env.axiomsUsed stt.store hash |>.toList
+    test s!"{root} relies on the axioms {expected}" (axioms == expected)
+
+/-- Asserts whether the constant `root` relies on `sorryAx` -/
+def extractUsesSorryTests (root : Name) (expected : Bool) : Extractor := fun stt =>
+  withOptionSome s!"{root} is found" (stt.env.getConst? root) fun hash =>
+    let descr := if expected then s!"{root} uses sorry" else s!"{root} doesn't use sorry"
+    test descr (stt.env.usesSorry stt.store hash == expected)
+
+/--
+Asserts that each pair of constants unfolds to definitionally equal values,
+reporting both reduced values otherwise
+-/
+def extractDefEqTests (pairs : List (Name × Name)) : Extractor := fun stt =>
+  pairs.foldl (init := .done) fun tSeq (x, y) => tSeq ++
+    withOptionSome s!"{x} is found" (stt.env.getConst? x) fun f =>
+      withOptionSome s!"{y} is found" (stt.env.getConst? y) fun g =>
+        withExceptOk s!"{x} ≡ {y}" (checkDefEq stt.store stt.env.constNames f g)
+          fun _ => .done
+
+/-- The named constants that `f` depends on, looking through unnamed ones such as blocks -/
+partial def namedDeps (store : Store) (names : ConstNames) (f : Lurk.F) : List Lurk.F :=
+  (store.find? f |>.map (·.deps.toList) |>.getD []).bind fun dep =>
+    if names.contains dep then [dep] else namedDeps store names dep
+
+/-- Asserts that `ppStore` prints every named constant after the ones it depends on -/
+def extractPrintingOrderTests : Extractor := fun stt =>
+  let names : ConstNames := stt.env.constNames
+  withExceptOk "Printing the store succeeds" (ppStore stt.store names) fun fmt =>
+    let out := fmt.pretty
+    let position (name : Name) : Option Nat :=
+      match out.splitOn s!"-- {name}\n" with
+      | before :: _ :: _ => some before.length
+      | _ => none
+    names.foldl (init := .done) fun tSeq f name =>
+      (namedDeps stt.store names f).foldl (init := tSeq) fun tSeq dep =>
+        let depName := names.getF dep
+        tSeq ++ test s!"{depName} is printed before {name}"
+          (match position depName, position name with
+            | some i, some j => i < j
+            | _, _ => false)
+
+/--
+Asserts that the constants declared by `init_quot` have the types that
+`Quotient` synthesizes from the hashes of `Quot`, `Quot.mk` and `Eq`
+-/
+def extractQuotTypeTests : Extractor := fun stt =>
+  let hash (name : Name) := stt.env.getConst? name |>.getD default
+  let expected : List (Name × Expr) := [
+    (`Quot, Quotient.quotType),
+    (`Quot.mk, Quotient.mkType (hash `Quot)),
+    (`Quot.lift, Quotient.liftType (hash `Quot) (hash `Eq)),
+    (`Quot.ind, Quotient.indType (hash `Quot) (hash `Quot.mk))]
+  expected.foldl (init := .done) fun tSeq (name, type) => tSeq ++
+    withOptionSome s!"{name} is found" (stt.env.getConst? name) fun f =>
+      withOptionSome s!"{name} is in the store" (stt.store.find? f) fun const =>
+        test s!"{name} has the synthesized type" (const.type? == some type)
+
+/--
+Asserts that each pair of constants unfolds to values that aren't definitionally
+equal, and that the error mentions each of the given parts, such as the reduced values
+-/
+def extractNotDefEqTests (pairs : List (Name × Name × List String)) : Extractor := fun stt =>
+  pairs.foldl (init := .done) fun tSeq (x, y, parts) => tSeq ++
+    withOptionSome s!"{x} is found" (stt.env.getConst? x) fun f =>
+      withOptionSome s!"{y} is found" (stt.env.getConst? y) fun g =>
+        withExceptErrorContaining s!"{x} ≢ {y}" (checkDefEq stt.store stt.env.constNames f g)
+          parts
+
 section AnonHashGroups
 
 /-
@@ -67,7 +220,7 @@ def extractAnonGroups (groups : List (List Name)) (stt : ContAddrState) :
   for group in groups do
     let mut hashGroup : Array (Name × Lurk.F) := #[]
     for name in group do
-      match stt.env.consts.find? name with
+      match stt.env.getConst? name with
       | none   => notFound  := notFound.push name
       | some h => hashGroup := hashGroup.push (name, h)
     hashGroups := hashGroups.push hashGroup
@@ -96,7 +249,7 @@ def extractLurkTypecheckTests (decls : List Name) : IOExtractor := fun stt => do
     return withExceptOk "Store extraction succeeds"
         (stt.ldonHashState.extractComms env.hashes) fun store =>
       decls.foldl (init := .done) fun tSeq decl => tSeq ++
-        withOptionSome s!"{decl} hash is found" (env.consts.find? decl) fun hash =>
+        withOptionSome s!"{decl} hash is found" (env.getConst? decl) fun hash =>
           let expr := mkRawTypecheckingExpr tcExpr hash
           withExceptOk s!"Typechecking {decl} succeeds" (expr.evaluate' store) fun v =>
             test s!"{decl} typechecks" (v == true)
@@ -34,7 +34,9 @@ import Yatima.CodeGen.Simp
 import Yatima.Common.GenTypechecker
 import Yatima.Common.IO
 import Yatima.Common.LightData
+import Yatima.Common.ToDot
 import Yatima.Common.ToLDON
+import Yatima.Common.TopoSort
 import Yatima.ContAddr.ContAddr
 import Yatima.ContAddr.ContAddrError
 import Yatima.ContAddr.ContAddrM
@@ -6,7 +6,8 @@ open System Yatima.ContAddr
 
 def primConstNames : Std.RBSet Lean.Name compare := .ofList [
   ``Nat, ``Bool, ``Bool.true, ``Bool.false, ``Nat.zero, ``String,
-  ``Nat.add, ``Nat.mul, ``Nat.pow, ``Nat.beq, ``Nat.ble, ``Nat.blt, ``Nat.succ
+  ``Nat.add, ``Nat.mul, ``Nat.pow, ``Nat.beq, ``Nat.ble, ``Nat.blt, ``Nat.succ,
+  ``String.append, ``String.length
 ] _
 
 def allowedAxiomNames : Std.RBSet Lean.Name compare := .ofList [
@@ -33,6 +34,8 @@ def nameToPrimRepr : Lean.Name → String
   | ``Nat.blt    => ".op .natBlt"
   | ``Nat.ble    => ".op .natBle"
   | ``Nat.succ   => ".op .natSucc"
+  | ``String.append => ".op .strAppend"
+  | ``String.length => ".op .strLength"
   | x => panic! s!"Invalid name: {x}"
 
 def formatMatchesP2F (pairs : List (Lean.Name × Lurk.F)) : List String :=
@@ -52,13 +55,12 @@ def pinRun (_p : Cli.Parsed) : IO UInt32 := do
   let leanEnv ← Lean.runFrontend primsInput default
   let (constMap, delta) := leanEnv.getConstsAndDelta
 
-  let commits ← match ← contAddr constMap delta false false with
-    | .error err => IO.eprintln err; return 1
-    | .ok stt => pure $ stt.env.consts.toList
+  -- Content-addressing errors are thrown as `IO.Error`s
+  let stt ← contAddr constMap delta false false
+  let commits := (← stt).env.constsList
 
-  let commitsQuick ← match ← contAddr constMap delta true false with
-    | .error err => IO.eprintln err; return 1
-    | .ok stt => pure $ stt.env.consts.toList
+  let sttQuick ← contAddr constMap delta true false
+  let commitsQuick := (← sttQuick).env.constsList
 
   let primCommits := commits.filter fun (n, _) => primConstNames.contains n
   let primCommitsQuick := commitsQuick.filter fun (n, _) => primConstNames.contains n
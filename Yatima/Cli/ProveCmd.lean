@@ -19,7 +19,7 @@ def proveRun (p : Cli.Parsed) : IO UInt32 := do
     | IO.eprintln "Environment file not provided"; return 1
   let some (env : Yatima.IR.Env) ← loadData envFileName false | return 1
 
-  let some declComm := env.consts.find? decl
+  let some declComm := env.getConst? decl
     | IO.eprintln s!"{decl} not found in the environment"; return 1
 
   let storeFileName : System.FilePath :=
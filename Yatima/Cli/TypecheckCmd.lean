@@ -2,12 +2,23 @@ import Cli.Basic
 import Yatima.Cli.Utils
 import Yatima.ContAddr.ContAddr
 import Yatima.Typechecker.Typechecker
+import Lean.Data.Json
+
+open Lean (Json toJson) in
+/-- The JSON object describing the result of typechecking a source file -/
+def typecheckResultToJson (source : String) : Except String Unit → Json
+  | .ok _ => .mkObj [("source", toJson source), ("ok", toJson true)]
+  | .error err => .mkObj [("source", toJson source), ("ok", toJson false), ("error", toJson err)]
 
 open System Yatima.ContAddr Yatima.Typechecker in
 def typecheckRun (p : Cli.Parsed) : IO UInt32 := do
   -- Get Lean source file name
   let some source := p.positionalArg? "source" |>.map (·.value)
     | IO.eprintln "No source was provided"; return 1
+  let json := p.hasFlag "json"
+  -- Timings are only reported when the output isn't JSON
+  let stopClock (cronos : Cronos) (tag : String) : IO Cronos :=
+    if json then pure cronos else cronos.clock! tag
 
   let mut cronos := Cronos.new
 
@@ -17,25 +28,35 @@ def typecheckRun (p : Cli.Parsed) : IO UInt32 := do
   let path := ⟨source⟩
   let leanEnv ← Lean.runFrontend (← IO.FS.readFile path) path
   let (constMap, delta) := leanEnv.getConstsAndDelta
-  cronos ← cronos.clock! "Run Lean frontend"
+  cronos ← stopClock cronos "Run Lean frontend"
 
   -- Start content-addressing
   cronos ← cronos.clock "Content-address"
   let stt ← match ← contAddr constMap delta true false with
-    | .error err => IO.eprintln err; return 1
+    | .error err =>
+      if json then IO.println (typecheckResultToJson source (.error $ toString err)).compress
+      else IO.eprintln err
+      return 1
     | .ok stt => pure stt
-  cronos ← cronos.clock! "Content-address"
+  cronos ← stopClock cronos "Content-address"
 
   -- Typecheck
   cronos ← cronos.clock "Typecheck"
-  match typecheckAll stt.store stt.env.constNames with
-  | .error err => IO.eprintln err; return 1
-  | .ok _ => cronos ← cronos.clock! "Typecheck"; return 0
+  let res := typecheckAll stt.store stt.env.constNames
+  if json then IO.println (typecheckResultToJson source res).compress
+  match res with
+  | .error err =>
+    unless json do IO.eprintln err
+    return 1
+  | .ok _ => cronos ← stopClock cronos "Typecheck"; return 0
 
 def typecheckCmd : Cli.Cmd := `[Cli|
   tc VIA typecheckRun;
   "Typechecks all constants in a Lean source file using cheap hashes"
 
+  FLAGS:
+    j, "json"; "Prints the result as a JSON object to the standard output"
+
   ARGS:
     source : String; "Lean source file"
 ]
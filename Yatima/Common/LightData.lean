@@ -134,31 +134,48 @@ instance : Encodable Inductive LightData where
         ← dec h, ← dec i, ← dec j⟩
     | x => throw s!"Invalid encoding for IR.Inductive: {x}"
 
+private def encodeConst : Const → LightData
+  | .mutIndBlock x => .cell #[false, x]
+  | .mutDefBlock x => .cell #[true,  x]
+  | .axiom          ⟨a, b⟩ => .cell #[0, a, b]
+  | .inductiveProj  ⟨a, b⟩ => .cell #[1, a, b]
+  | .definitionProj ⟨a, b⟩ => .cell #[2, a, b]
+  | .theorem         ⟨a, b, c⟩ => .cell #[0, a, b, c]
+  | .opaque          ⟨a, b, c⟩ => .cell #[1, a, b, c]
+  | .quotient        ⟨a, b, c⟩ => .cell #[2, a, b, c]
+  | .constructorProj ⟨a, b, c⟩ => .cell #[3, a, b, c]
+  | .recursorProj    ⟨a, b, c⟩ => .cell #[4, a, b, c]
+  | .definition ⟨a, b, c, d⟩ => .cell #[false, a, b, c, d]
+
+private def decodeConst : LightData → Except String Const
+  | .cell #[false, x] => return .mutIndBlock (← dec x)
+  | .cell #[true,  x] => return .mutDefBlock (← dec x)
+  | .cell #[0, a, b] => return .axiom          ⟨← dec a, ← dec b⟩
+  | .cell #[1, a, b] => return .inductiveProj  ⟨← dec a, ← dec b⟩
+  | .cell #[2, a, b] => return .definitionProj ⟨← dec a, ← dec b⟩
+  | .cell #[0, a, b, c] => return .theorem         ⟨← dec a, ← dec b, ← dec c⟩
+  | .cell #[1, a, b, c] => return .opaque          ⟨← dec a, ← dec b, ← dec c⟩
+  | .cell #[2, a, b, c] => return .quotient        ⟨← dec a, ← dec b, ← dec c⟩
+  | .cell #[3, a, b, c] => return .constructorProj ⟨← dec a, ← dec b, ← dec c⟩
+  | .cell #[4, a, b, c] => return .recursorProj    ⟨← dec a, ← dec b, ← dec c⟩
+  | .cell #[false, a, b, c, d] => return .definition ⟨← dec a, ← dec b, ← dec c, ← dec d⟩
+  | x => throw s!"Invalid encoding for IR.Const: {x}"
+
+/--
+The version of the encoding of `IR.Const`, which prefixes the encoded data of
+every constant so that stored constants are versioned like `IR.Env`. It must
+be bumped whenever that encoding changes
+-/
+def constFormatVersion : Nat := 1
+
 instance : Encodable Const LightData where
-  encode
-    | .mutIndBlock x => .cell #[false, x]
-    | .mutDefBlock x => .cell #[true,  x]
-    | .axiom          ⟨a, b⟩ => .cell #[0, a, b]
-    | .inductiveProj  ⟨a, b⟩ => .cell #[1, a, b]
-    | .definitionProj ⟨a, b⟩ => .cell #[2, a, b]
-    | .theorem         ⟨a, b, c⟩ => .cell #[0, a, b, c]
-    | .opaque          ⟨a, b, c⟩ => .cell #[1, a, b, c]
-    | .quotient        ⟨a, b, c⟩ => .cell #[2, a, b, c]
-    | .constructorProj ⟨a, b, c⟩ => .cell #[3, a, b, c]
-    | .recursorProj    ⟨a, b, c⟩ => .cell #[4, a, b, c]
-    | .definition ⟨a, b, c, d⟩ => .cell #[false, a, b, c, d]
+  encode x := .cell #[constFormatVersion, encodeConst x]
   decode
-    | .cell #[false, x] => return .mutIndBlock (← dec x)
-    | .cell #[true,  x] => return .mutDefBlock (← dec x)
-    | .cell #[0, a, b] => return .axiom          ⟨← dec a, ← dec b⟩
-    | .cell #[1, a, b] => return .inductiveProj  ⟨← dec a, ← dec b⟩
-    | .cell #[2, a, b] => return .definitionProj ⟨← dec a, ← dec b⟩
-    | .cell #[0, a, b, c] => return .theorem         ⟨← dec a, ← dec b, ← dec c⟩
-    | .cell #[1, a, b, c] => return .opaque          ⟨← dec a, ← dec b, ← dec c⟩
-    | .cell #[2, a, b, c] => return .quotient        ⟨← dec a, ← dec b, ← dec c⟩
-    | .cell #[3, a, b, c] => return .constructorProj ⟨← dec a, ← dec b, ← dec c⟩
-    | .cell #[4, a, b, c] => return .recursorProj    ⟨← dec a, ← dec b, ← dec c⟩
-    | .cell #[false, a, b, c, d] => return .definition ⟨← dec a, ← dec b, ← dec c, ← dec d⟩
+    | .cell #[v, x] => do
+      let v : Nat ← dec v
+      if v != constFormatVersion then
+        throw s!"Unsupported IR.Const format version {v}, expected {constFormatVersion}"
+      decodeConst x
     | x => throw s!"Invalid encoding for IR.Const: {x}"
 
 instance [Encodable (Array (α × β)) LightData] [Ord α] :
@@ -171,10 +188,29 @@ instance [Encodable (Array α) LightData] [Ord α] :
   encode x := (x.foldl (·.push ·) #[] : Array α)
   decode x := return .ofArray (← dec x) _
 
+/--
+The version of the encoding of `IR.Env`, which prefixes the encoded data. It
+must be bumped whenever that encoding changes, so older data is rejected
+instead of misread
+-/
+def envFormatVersion : Nat := 1
+
 instance : Encodable IR.Env LightData where
-  encode | ⟨x, y⟩ => .cell #[x, y]
+  encode | ⟨x, y⟩ => .cell #[envFormatVersion, x, y]
   decode
-    | .cell #[x, y] => return ⟨← dec x, ← dec y⟩
-    | x => throw s!"Invalid encoding for IR.Definition: {x}"
+    | .cell #[v, x, y] => do
+      let v : Nat ← dec v
+      if v != envFormatVersion then
+        throw s!"Unsupported IR.Env format version {v}, expected {envFormatVersion}"
+      return ⟨← dec x, ← dec y⟩
+    | .cell #[_, _] => throw "IR.Env encoded without a format version"
+    | x => throw s!"Invalid encoding for IR.Env: {x}"
+
+/--
+The number of bytes `a` occupies once encoded, which is the size of the file
+`dumpData` would write for it. Useful to bound what gets stored
+-/
+def encodedSize [h : Encodable α LightData] (a : α) : Nat :=
+  (h.encode a).toByteArray.size
 
 end Yatima.ContAddr
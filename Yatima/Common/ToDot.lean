@@ -0,0 +1,50 @@
+import Yatima.Datatypes.Expr
+
+/-!
+# Expressions as DOT graphs
+
+This module renders Yatima IR expressions in Graphviz's DOT language. Equal
+subexpressions are shared, as they would be when content-addressed, so they
+appear as a single node with several incoming edges.
+-/
+
+namespace Yatima.IR
+
+private def Expr.dotLabel : Expr → String
+  | .var idx _ => s!"var {idx}"
+  | .sort _ => "sort"
+  | .const f _ => s!"const {f}"
+  | .app .. => "app"
+  | .lam .. => "lam"
+  | .pi .. => "pi"
+  | .letE .. => "let"
+  | .lit (.natVal n) => toString n
+  | .lit (.strVal s) => s.quote
+  | .proj idx _ => s!"proj {idx}"
+
+private def Expr.children : Expr → List Expr
+  | .app e₁ e₂
+  | .lam e₁ e₂
+  | .pi  e₁ e₂ => [e₁, e₂]
+  | .letE e₁ e₂ e₃ => [e₁, e₂, e₃]
+  | .proj _ e => [e]
+  | _ => []
+
+/-- Emits the node of an expression, if new, and returns its identifier -/
+private partial def Expr.toDotAux (e : Expr) :
+    StateM (Std.RBMap Expr Nat compare × Array String) Nat := do
+  if let some id := (← get).1.find? e then return id
+  let children ← e.children.mapM Expr.toDotAux
+  modifyGet fun (nodes, lines) =>
+    let id := nodes.size
+    let lines := lines.push s!"  n{id} [label={e.dotLabel.quote}];"
+    let lines := children.enum.foldl (init := lines) fun lines (i, child) =>
+      lines.push s!"  n{id} -> n{child} [label={i}];"
+    (id, (nodes.insert e id, lines))
+
+/-- Renders an expression as a DOT graph in which equal subexpressions are shared -/
+def Expr.toDot (e : Expr) : String :=
+  let (_, _, lines) := e.toDotAux.run (.empty, #[])
+  "digraph {\n" ++ "\n".intercalate lines.toList ++ "\n}\n"
+
+end Yatima.IR
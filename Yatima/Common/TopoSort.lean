@@ -0,0 +1,23 @@
+import Std.Data.RBMap
+
+namespace Yatima
+
+private partial def topoVisit [Ord α] [ToString α] (deps : α → List α)
+    (path : Std.RBSet α compare) (x : α) :
+    StateT (Std.RBSet α compare × Array α) (Except String) Unit := do
+  if (← get).1.contains x then return
+  if path.contains x then throw s!"Dependency cycle found through {x}"
+  let path := path.insert x
+  for dep in deps x do topoVisit deps path dep
+  modify fun (done, order) => (done.insert x, order.push x)
+
+/--
+Sorts the nodes reachable from `roots` such that every node comes after the
+ones returned by `deps` for it. Throws an error if there's a dependency cycle.
+-/
+def topoSort [Ord α] [ToString α] (roots : List α) (deps : α → List α) :
+    Except String (Array α) := do
+  let (_, _, order) ← roots.forM (topoVisit deps .empty) |>.run (.empty, #[])
+  return order
+
+end Yatima
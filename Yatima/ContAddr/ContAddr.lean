@@ -71,7 +71,7 @@ def isInternalRec (expr : Lean.Expr) (name : Lean.Name) : Bool :=
 mutual
 
 partial def contAddrConst (const : Lean.ConstantInfo) : ContAddrM Lurk.F := do
-  match (← get).env.consts.find? const.name with
+  match (← get).env.getConst? const.name with
   | some hash => pure hash
   | none => match const with
     | .defnInfo val => withLevelsAndReset val.levelParams $ contAddrDefinition val
@@ -34,4 +34,13 @@ instance : ToString ContAddrError where toString
     s!"Non-recursor '{n}' extracted from children"
   | .cantFindMutDefIndex n => s!"Can't find index for mutual definition '{n}'"
 
+/-- Allows `ContAddrError`s to be thrown in `IO`, e.g. with `throw (err : IO.Error)` -/
+instance : Coe ContAddrError IO.Error where
+  coe err := .userError (toString err)
+
+instance : MonadLift (Except ContAddrError) IO where
+  monadLift
+    | .ok a => pure a
+    | .error err => throw (err : IO.Error)
+
 end Yatima.ContAddr
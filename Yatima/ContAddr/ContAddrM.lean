@@ -52,27 +52,39 @@ def withRecrs (recrCtx : RBMap Name Nat compare) :
 def withLevels (lvls : List Name) : ContAddrM α → ContAddrM α :=
   withReader $ fun c => { c with univCtx := lvls }
 
+/-- The hash of a constant: either a cheap one or its LDON commitment -/
+def hashConst (const : Const) (quick : Bool) (ldonHashState : Lurk.Scalar.LDONHashState) :
+    Lurk.F × Lurk.Scalar.LDONHashState :=
+  if quick then (.ofNat $ (Hashable.hash const).toNat, ldonHashState)
+  else const.toLDON.commit ldonHashState
+
 open System (FilePath) in
 def commit (const : Const) : ContAddrM Lurk.F := do
   match (← get).commits.find? const with
   | some hash => pure hash
   | none =>
-    if (← read).quick then
-      let hash := .ofNat $ (Hashable.hash const).toNat
-      modifyGet fun stt => (hash, { stt with
-        commits := stt.commits.insert const hash })
-    else
-      let (hash, encStt) := const.toLDON.commit (← get).ldonHashState
-      modifyGet fun stt => (hash, { stt with
-        commits := stt.commits.insert const hash
-        ldonHashState := encStt })
+    let (hash, encStt) := hashConst const (← read).quick (← get).ldonHashState
+    modifyGet fun stt => (hash, { stt with
+      commits := stt.commits.insert const hash
+      ldonHashState := encStt })
+
+/--
+Recomputes the hash of every constant in a store and returns the hashes that
+don't match the constants they're mapped to. Every mismatch is reported, not
+just the first one
+-/
+def verifyStore (store : Std.RBMap Lurk.F Const compare) (quick : Bool)
+    (ldonHashState : Lurk.Scalar.LDONHashState := default) : Array Lurk.F :=
+  let (mismatches, _) := store.foldl (init := (#[], ldonHashState))
+    fun (mismatches, ldonHashState) hash const =>
+      let (hash', ldonHashState) := hashConst const quick ldonHashState
+      (if hash' == hash then mismatches else mismatches.push hash, ldonHashState)
+  mismatches
 
 @[inline] def addConstToEnv (name : Name) (hash : Lurk.F) : ContAddrM Unit :=
-  modify fun stt => { stt with env := { stt.env with
-    consts := stt.env.consts.insert name hash } }
+  modify fun stt => { stt with env := stt.env.insertConst name hash }
 
 @[inline] def addBlockToEnv (hash : Lurk.F) : ContAddrM Unit :=
-  modify fun stt => { stt with env := { stt.env with
-    blocks := stt.env.blocks.insert hash } }
+  modify fun stt => { stt with env := stt.env.insertBlock hash }
 
 end Yatima.ContAddr
@@ -27,6 +27,55 @@ structure Quotient where
   kind : QuotKind
   deriving Ord, BEq, Hashable, Repr
 
+namespace Quotient
+
+private def bvar (idx : Nat) : Expr :=
+  .var idx []
+
+/-- `α → α → Prop`, assuming `α` is the variable of index 0 -/
+private def relType : Expr :=
+  .pi (bvar 0) $ .pi (bvar 1) $ .sort .zero
+
+/-- `@Quot.{u} α r`, given the indices of `α` and `r` -/
+private def quotApp (quotF : Lurk.F) (α r : Nat) : Expr :=
+  .app (.app (.const quotF [.var 0]) (bvar α)) (bvar r)
+
+/-- `Quot.{u} : {α : Sort u} → (α → α → Prop) → Sort u` -/
+def quotType : Expr :=
+  .pi (.sort (.var 0)) $ .pi relType $ .sort (.var 0)
+
+/-- `Quot.mk.{u} : {α : Sort u} → (r : α → α → Prop) → α → @Quot α r` -/
+def mkType (quotF : Lurk.F) : Expr :=
+  .pi (.sort (.var 0)) $ .pi relType $ .pi (bvar 1) $ quotApp quotF 2 1
+
+/--
+```
+Quot.lift.{u, v} : {α : Sort u} → {r : α → α → Prop} → {β : Sort v} →
+  (f : α → β) → (∀ (a b : α), r a b → @Eq β (f a) (f b)) → @Quot α r → β
+```
+-/
+def liftType (quotF eqF : Lurk.F) : Expr :=
+  .pi (.sort (.var 0)) $ .pi relType $ .pi (.sort (.var 1)) $
+    .pi (.pi (bvar 2) (bvar 1)) $
+    .pi (.pi (bvar 3) $ .pi (bvar 4) $ .pi (.app (.app (bvar 4) (bvar 1)) (bvar 0)) $
+      .app (.app (.app (.const eqF [.var 1]) (bvar 4)) (.app (bvar 3) (bvar 2)))
+        (.app (bvar 3) (bvar 1))) $
+    .pi (quotApp quotF 4 3) (bvar 3)
+
+/--
+```
+Quot.ind.{u} : ∀ {α : Sort u} {r : α → α → Prop} {β : @Quot α r → Prop},
+  (∀ (a : α), β (@Quot.mk α r a)) → ∀ (q : @Quot α r), β q
+```
+-/
+def indType (quotF mkF : Lurk.F) : Expr :=
+  .pi (.sort (.var 0)) $ .pi relType $ .pi (.pi (quotApp quotF 1 0) (.sort .zero)) $
+    .pi (.pi (bvar 2) $ .app (bvar 1) $
+      .app (.app (.app (.const mkF [.var 0]) (bvar 3)) (bvar 2)) (bvar 0)) $
+    .pi (quotApp quotF 3 2) $ .app (bvar 2) (bvar 0)
+
+end Quotient
+
 structure Definition where
   lvls  : Nat
   type  : Expr
@@ -42,6 +91,21 @@ structure Constructor where
   fields : Nat
   deriving Ord, BEq, Hashable, Repr
 
+/--
+The types of the fields of a constructor, which are the domains of the binders
+of its type that come after the parameters. Each field type may refer to the
+parameters and to the fields before it as loose bound variables. Returns `none`
+if the type doesn't have `params + fields` binders
+-/
+def Constructor.fieldTypes (ctor : Constructor) : Option (List Expr) :=
+  go ctor.params ctor.fields ctor.type
+where
+  go : Nat → Nat → Expr → Option (List Expr)
+    | 0, 0, _ => some []
+    | 0, n + 1, .pi dom img => return dom :: (← go 0 n img)
+    | p + 1, n, .pi _ img => go p n img
+    | _, _, _ => none
+
 structure RecursorRule where
   fields : Nat
   rhs    : Expr
@@ -59,6 +123,23 @@ structure Recursor where
   internal : Bool
   deriving Ord, BEq, Hashable, Repr
 
+/--
+The sort that the motives of a recursor eliminate into, read from the codomain
+of the type of its first motive. Returns `none` if the type of the recursor
+doesn't have that shape
+-/
+def Recursor.motiveSort? (recr : Recursor) : Option Univ :=
+  skip recr.params recr.type
+where
+  skip : Nat → Expr → Option Univ
+    | 0, .pi motive _ => codomain motive
+    | n + 1, .pi _ img => skip n img
+    | _, _ => none
+  codomain : Expr → Option Univ
+    | .pi _ img => codomain img
+    | .sort u => some u
+    | _ => none
+
 structure Inductive where
   lvls    : Nat
   type    : Expr
@@ -74,6 +155,25 @@ structure Inductive where
   unit    : Bool
   deriving Inhabited, Ord, BEq, Hashable, Repr
 
+/--
+The sort an inductive lives in, read from the codomain of its type after all
+parameters and indices. Returns `none` if the type doesn't end in a sort
+-/
+def Inductive.resultSort? (ind : Inductive) : Option Univ :=
+  go ind.type
+where
+  go : Expr → Option Univ
+    | .pi _ img => go img
+    | .sort u => some u
+    | _ => none
+
+/--
+Whether an inductive is a proposition. Inductives whose sort is only `Prop` for
+some instantiations of their universe variables aren't considered propositions
+-/
+def Inductive.isProp (ind : Inductive) : Bool :=
+  ind.resultSort? == some .zero
+
 structure InductiveProj where
   block : Lurk.F
   idx   : Nat
@@ -116,4 +216,79 @@ def Const.isMutType : Const → Bool
   | .mutDefBlock _ | .mutIndBlock _ => true
   | _ => false
 
+def Const.ctorName : Const → String
+  | .axiom           _ => "axiom"
+  | .theorem         _ => "theorem"
+  | .opaque          _ => "opaque"
+  | .definition      _ => "definition"
+  | .quotient        _ => "quotient"
+  | .inductiveProj   _ => "inductiveProj"
+  | .constructorProj _ => "constructorProj"
+  | .recursorProj    _ => "recursorProj"
+  | .definitionProj  _ => "definitionProj"
+  | .mutDefBlock     _ => "mutDefBlock"
+  | .mutIndBlock     _ => "mutIndBlock"
+
+/--
+The type of a constant that holds it directly. Projections need their mutual
+blocks to be dereferenced, so they return `none`, as well as the blocks
+themselves
+-/
+def Const.type? : Const → Option Expr
+  | .axiom      x
+  | .theorem    x
+  | .opaque     x
+  | .definition x
+  | .quotient   x => some x.type
+  | _ => none
+
+/--
+Instantiates the universe variables of a constant with `univs`, which must have
+one universe for each of them, resulting in a constant without universe
+variables. Projections and mutual blocks aren't supported, since the members of
+a block are instantiated with the universes of each use
+-/
+def Const.instantiateUnivs (univs : List Univ) (const : Const) : Except String Const := do
+  let check (lvls : Nat) : Except String Unit :=
+    if lvls != univs.length then
+      throw s!"Expected {lvls} universes, but got {univs.length}"
+    else pure ()
+  let inst := Expr.instantiateUnivs univs
+  match const with
+  | .axiom x =>
+    check x.lvls; return .axiom { x with lvls := 0, type := inst x.type }
+  | .theorem x =>
+    check x.lvls; return .theorem { x with lvls := 0, type := inst x.type, value := inst x.value }
+  | .opaque x =>
+    check x.lvls; return .opaque { x with lvls := 0, type := inst x.type, value := inst x.value }
+  | .definition x =>
+    check x.lvls; return .definition { x with lvls := 0, type := inst x.type, value := inst x.value }
+  | .quotient x =>
+    check x.lvls; return .quotient { x with lvls := 0, type := inst x.type }
+  | _ => throw s!"Can't instantiate the universes of a {const.ctorName}"
+
+/--
+The hashes of the constants that a constant directly refers to. Projections
+only refer to their mutual blocks, which in turn refer to everything that their
+members mention
+-/
+def Const.deps : Const → Std.RBSet Lurk.F compare
+  | .axiom    x
+  | .quotient x => x.type.constRefs .empty
+  | .theorem    x
+  | .opaque     x
+  | .definition x => x.value.constRefs $ x.type.constRefs .empty
+  | .inductiveProj   ⟨block, ..⟩
+  | .constructorProj ⟨block, ..⟩
+  | .recursorProj    ⟨block, ..⟩
+  | .definitionProj  ⟨block, ..⟩ => Std.RBSet.empty.insert block
+  | .mutDefBlock defs => defs.foldl (init := .empty) fun acc defn =>
+    defn.value.constRefs $ defn.type.constRefs acc
+  | .mutIndBlock inds => inds.foldl (init := .empty) fun acc ind =>
+    let acc := ind.ctors.foldl (init := ind.type.constRefs acc) fun acc ctor =>
+      ctor.type.constRefs acc
+    ind.recrs.foldl (init := acc) fun acc recr =>
+      recr.rules.foldl (init := recr.type.constRefs acc) fun acc rule =>
+        rule.rhs.constRefs acc
+
 end Yatima.IR
@@ -1,5 +1,5 @@
 import Std.Data.RBMap
-import Yatima.Datatypes.Lean
+import Yatima.Datatypes.Const
 import YatimaStdLib.ByteVector
 import Lurk.Field
 
@@ -11,10 +11,102 @@ structure Env where
   blocks : Std.RBSet Lurk.F compare
   deriving Inhabited
 
+namespace Env
+
+@[inline] def getConst? (env : Env) (name : Name) : Option Lurk.F :=
+  env.consts.find? name
+
+@[inline] def containsBlock (env : Env) (hash : Lurk.F) : Bool :=
+  env.blocks.contains hash
+
+@[inline] def insertConst (env : Env) (name : Name) (hash : Lurk.F) : Env :=
+  { env with consts := env.consts.insert name hash }
+
+@[inline] def insertBlock (env : Env) (hash : Lurk.F) : Env :=
+  { env with blocks := env.blocks.insert hash }
+
+/-- Folds over the named constants of the environment and their hashes, in name order -/
+@[inline] def foldConsts (env : Env) (init : σ) (f : σ → Name → Lurk.F → σ) : σ :=
+  env.consts.foldl f init
+
+/-- The named constants of the environment and their hashes, in name order -/
+@[inline] def constsList (env : Env) : List (Name × Lurk.F) :=
+  env.consts.toList
+
+/--
+Renames a constant. Its hash is kept, since hashes don't depend on names, so
+nothing else needs updating. Fails if `oldName` isn't in the environment or if
+`newName` already is
+-/
+def rename (env : Env) (oldName newName : Name) : Except String Env := do
+  let some hash := env.getConst? oldName
+    | throw s!"Constant {oldName} not found in the environment"
+  if env.consts.contains newName then
+    throw s!"Constant {newName} is already in the environment"
+  return { env with consts := env.consts.erase oldName |>.insert newName hash }
+
+/--
+The names and hashes of the constants of the environment that satisfy `p`, as
+found in `store`. Constants that aren't in `store` are left out
+-/
+def filterConsts (env : Env) (store : Std.RBMap Lurk.F Const compare)
+    (p : Const → Bool) : Array (Name × Lurk.F) :=
+  env.foldConsts #[] fun acc name hash =>
+    match store.find? hash with
+    | some const => if p const then acc.push (name, hash) else acc
+    | none => acc
+
+/--
+The names of the environment that map to hashes missing from `store`, which
+can't be resolved to constants
+-/
+def danglingNames (env : Env) (store : Std.RBMap Lurk.F Const compare) : Array Name :=
+  env.foldConsts #[] fun acc name hash =>
+    if store.contains hash then acc else acc.push name
+
+end Env
+
 @[inline] def Env.hashes (env : Env) : Array Lurk.F :=
   env.consts.valuesArray ++ env.blocks.foldl (·.push ·) #[]
 
 @[inline] def Env.constNames (env : Env) : Std.RBMap Lurk.F Name compare :=
-  env.consts.foldl (init := .empty) fun acc n f => acc.insert f n
+  env.foldConsts .empty fun acc n f => acc.insert f n
+
+private partial def reachableAux (store : Std.RBMap Lurk.F Const compare)
+    (acc : Std.RBSet Lurk.F compare) (hash : Lurk.F) : Std.RBSet Lurk.F compare :=
+  if acc.contains hash then acc else
+  let acc := acc.insert hash
+  match store.find? hash with
+  | some const => const.deps.foldl (reachableAux store) acc
+  | none => acc
+
+/--
+The hashes of the constants that the constant of hash `root` depends on,
+directly or transitively, as found in `store`. Includes `root` itself
+-/
+def reachable (store : Std.RBMap Lurk.F Const compare) (root : Lurk.F) :
+    Std.RBSet Lurk.F compare :=
+  reachableAux store .empty root
+
+/--
+The names of the axioms that the constant of hash `root` relies on, like Lean's
+`#print axioms`
+-/
+def Env.axiomsUsed (env : Env) (store : Std.RBMap Lurk.F Const compare)
+    (root : Lurk.F) : Std.RBSet Name compare :=
+  let names := env.constNames
+  (reachable store root).foldl (init := .empty) fun acc hash =>
+    match store.find? hash, names.find? hash with
+    | some (.axiom _), some name => acc.insert name
+    | _, _ => acc
+
+/--
+Whether the constant of hash `root` relies on any of the `placeholders` axioms,
+which stand for missing proofs. Defaults to Lean's `sorryAx`
+-/
+def Env.usesSorry (env : Env) (store : Std.RBMap Lurk.F Const compare) (root : Lurk.F)
+    (placeholders : List Name := [``sorryAx]) : Bool :=
+  let axioms := env.axiomsUsed store root
+  placeholders.any axioms.contains
 
 end Yatima.IR
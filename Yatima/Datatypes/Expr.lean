@@ -1,4 +1,5 @@
 import Yatima.Datatypes.Univ
+import Std.Data.RBMap
 import YatimaStdLib.Ord
 import Lurk.Field
 
@@ -22,4 +23,97 @@ inductive Expr
   | proj  : Nat → Expr → Expr
   deriving Inhabited, Ord, BEq, Hashable, Repr
 
+/-- Adds the hashes of the constants referenced by an expression to `acc` -/
+def Expr.constRefs : Expr → Std.RBSet Lurk.F compare → Std.RBSet Lurk.F compare
+  | .const f _, acc => acc.insert f
+  | .app e₁ e₂, acc
+  | .lam e₁ e₂, acc
+  | .pi  e₁ e₂, acc => e₂.constRefs $ e₁.constRefs acc
+  | .letE e₁ e₂ e₃, acc => e₃.constRefs $ e₂.constRefs $ e₁.constRefs acc
+  | .proj _ e, acc => e.constRefs acc
+  | _, acc => acc
+
+/--
+Adds `n` to the indices of the variables that are free at depth `cutoff`,
+that is, those of index greater than or equal to `cutoff`
+-/
+def Expr.liftLooseVars (n : Nat) (cutoff : Nat := 0) : Expr → Expr
+  | .var idx us => .var (if idx < cutoff then idx else idx + n) us
+  | .app fnc arg => .app (fnc.liftLooseVars n cutoff) (arg.liftLooseVars n cutoff)
+  | .lam dom bod => .lam (dom.liftLooseVars n cutoff) (bod.liftLooseVars n (cutoff + 1))
+  | .pi dom img => .pi (dom.liftLooseVars n cutoff) (img.liftLooseVars n (cutoff + 1))
+  | .letE typ val bod =>
+    .letE (typ.liftLooseVars n cutoff) (val.liftLooseVars n cutoff)
+      (bod.liftLooseVars n (cutoff + 1))
+  | .proj idx e => .proj idx (e.liftLooseVars n cutoff)
+  | e => e
+
+/--
+Replaces the variable of index `depth` by `val` and decrements the indices of
+the variables above it, as when removing the binder that `depth` points to.
+This is purely syntactic: nothing is evaluated
+-/
+def Expr.instantiate1 (e val : Expr) (depth : Nat := 0) : Expr := match e with
+  | .var idx us =>
+    if idx < depth then .var idx us
+    else if idx == depth then val.liftLooseVars depth
+    else .var (idx - 1) us
+  | .app fnc arg => .app (fnc.instantiate1 val depth) (arg.instantiate1 val depth)
+  | .lam dom bod => .lam (dom.instantiate1 val depth) (bod.instantiate1 val (depth + 1))
+  | .pi dom img => .pi (dom.instantiate1 val depth) (img.instantiate1 val (depth + 1))
+  | .letE typ val' bod =>
+    .letE (typ.instantiate1 val depth) (val'.instantiate1 val depth)
+      (bod.instantiate1 val (depth + 1))
+  | .proj idx e => .proj idx (e.instantiate1 val depth)
+  | e => e
+
+/--
+Replaces the variables of indices `depth` to `depth + vals.length - 1` by the
+values in `vals` at once, and decrements the indices of the variables above
+them by `vals.length`. Like the environment of the evaluator, `vals` lists the
+value of the innermost binder first, so instantiating `fun x y => b` with
+`[y', x']` is the same as instantiating it with `y'` and then with `x'` when
+`y'` has no loose variables (otherwise they must be lifted by one first)
+-/
+def Expr.instantiate (e : Expr) (vals : List Expr) (depth : Nat := 0) : Expr := match e with
+  | .var idx us =>
+    if idx < depth then .var idx us
+    else match vals.get? (idx - depth) with
+      | some val => val.liftLooseVars depth
+      | none => .var (idx - vals.length) us
+  | .app fnc arg => .app (fnc.instantiate vals depth) (arg.instantiate vals depth)
+  | .lam dom bod => .lam (dom.instantiate vals depth) (bod.instantiate vals (depth + 1))
+  | .pi dom img => .pi (dom.instantiate vals depth) (img.instantiate vals (depth + 1))
+  | .letE typ val bod =>
+    .letE (typ.instantiate vals depth) (val.instantiate vals depth)
+      (bod.instantiate vals (depth + 1))
+  | .proj idx e => .proj idx (e.instantiate vals depth)
+  | e => e
+
+/-- Instantiates the universe variables of an expression with `univs`, reducing the results -/
+def Expr.instantiateUnivs (univs : List Univ) : Expr → Expr
+  | .var idx us => .var idx (us.map (Univ.instBulkReduce univs))
+  | .sort u => .sort (Univ.instBulkReduce univs u)
+  | .const f us => .const f (us.map (Univ.instBulkReduce univs))
+  | .app fnc arg => .app (fnc.instantiateUnivs univs) (arg.instantiateUnivs univs)
+  | .lam dom bod => .lam (dom.instantiateUnivs univs) (bod.instantiateUnivs univs)
+  | .pi dom img => .pi (dom.instantiateUnivs univs) (img.instantiateUnivs univs)
+  | .letE typ val bod =>
+    .letE (typ.instantiateUnivs univs) (val.instantiateUnivs univs) (bod.instantiateUnivs univs)
+  | .proj idx e => .proj idx (e.instantiateUnivs univs)
+  | e@(.lit _) => e
+
+/-- The arguments that the head of an expression is applied to, in order -/
+def Expr.appArgs (e : Expr) : List Expr :=
+  go e []
+where
+  go : Expr → List Expr → List Expr
+    | .app fnc arg, acc => go fnc (arg :: acc)
+    | _, acc => acc
+
+/-- Contracts the head of `(fun _ => body) arg` into `body[0 := arg]` -/
+def Expr.betaHead? : Expr → Option Expr
+  | .app (.lam _ body) arg => some $ body.instantiate1 arg
+  | _ => none
+
 end Yatima.IR
@@ -19,8 +19,8 @@ inductive Univ
 namespace Univ
 
 /--
-Reduces as a `max` applied to two values: `max a 0 = max 0 a = a` and
-`max (succ a) (succ b) = succ (max a b)`.
+Reduces as a `max` applied to two values: `max a 0 = max 0 a = a`,
+`max (succ a) (succ b) = succ (max a b)` and `max a a = a`.
 It is assumed that `a` and `b` are already reduced
 -/
 def reduceMax (a b : Univ) : Univ :=
@@ -28,8 +28,7 @@ def reduceMax (a b : Univ) : Univ :=
   | .zero, _ => b
   | _, .zero => a
   | .succ a, .succ b => .succ (reduceMax a b)
-  | .var idx, .var idx' => if idx == idx' then a else .max a b
-  | _, _ => .max a b
+  | _, _ => if a == b then a else .max a b
 
 /--
 Reduces as an `imax` applied to two values.
@@ -86,7 +85,11 @@ def instReduce (u : Univ) (idx : Nat) (subst : Univ) : Univ :=
 
 /--
 Instantiate multiple variables at the same time and reduce. Assumes already
-reduced `substs`
+reduced `substs`.
+
+Never panics: a variable whose index is out of the range of `substs` is kept as
+a variable, shifted down by the number of substitutions, as if `substs` only
+instantiated the first variables of a larger universe context
 -/
 def instBulkReduce (substs : List Univ) : Univ → Univ
   | z@(.zero ..) => z
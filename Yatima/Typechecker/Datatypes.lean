@@ -75,8 +75,8 @@ inductive TypedConst
   | «inductive» : (type : TypedExpr) → (struct : Bool) → TypedConst
   | «opaque»    : (type value : TypedExpr) → TypedConst
   | definition  : (type deref : TypedExpr) → (part : Bool) → TypedConst
-  | constructor : (type : TypedExpr) → (idx fields : Nat) → TypedConst
-  | recursor    : (type : TypedExpr) → (params motives minors indices : Nat) → (k : Bool) → (indProj : InductiveProj) → (rules : Array (Nat × TypedExpr)) → TypedConst
+  | constructor : (type : TypedExpr) → (idx params fields : Nat) → TypedConst
+  | recursor    : (type : TypedExpr) → (params motives minors indices : Nat) → (k internal : Bool) → (indProj : InductiveProj) → (rules : Array (Nat × TypedExpr)) → TypedConst
   | quotient    : (type : TypedExpr) → (kind : QuotKind) → TypedConst
   deriving Inhabited, BEq
 
@@ -90,6 +90,34 @@ def TypedConst.type : TypedConst → TypedExpr
   | recursor    type ..
   | quotient    type .. => type
 
+/--
+The value a constant unfolds to during evaluation, if any. Only theorems and
+non-partial definitions unfold: opaque constants never do, like in Lean, and
+neither do axioms, inductives, constructors, recursors and quotients
+-/
+def TypedConst.unfold? : TypedConst → Option TypedExpr
+  | «theorem» _ deref => some deref
+  | definition _ deref false => some deref
+  | _ => none
+
+/--
+The number of arguments that a recursor takes before its major premise, which
+is also the position of the major premise among its arguments. Other constants
+have no major premise
+-/
+def TypedConst.majorIdx? : TypedConst → Option Nat
+  | recursor _ params motives minors indices .. => some (params + motives + minors + indices)
+  | _ => none
+
+/--
+The environment of an evaluation, holding the values of bound variables (the
+most recent binder first) and the universe levels of the constant being
+evaluated.
+
+`exprs` is a persistent list on purpose: extending an environment with a new
+binder is a cons, which is O(1) and shares the tail with the environment it was
+extended from, so closures capturing an environment never copy it.
+-/
 structure Env' (SusValue : Type) where
   exprs : List SusValue
   univs : List Univ
@@ -211,6 +239,7 @@ def mkSusVar (info : TypeInfo) (idx : Nat) : SusValue :=
 
 inductive PrimConstOp
   | natAdd | natMul | natPow | natBeq | natBle | natBlt  | natSucc
+  | strAppend | strLength
   deriving Ord, Repr
 
 inductive PrimConst
@@ -224,10 +253,13 @@ inductive PrimConst
   deriving Ord, Repr
 
 def PrimConstOp.numArgs : PrimConstOp → Nat
-  | .natAdd | .natMul | .natPow | .natBeq | .natBle | .natBlt => 2 | .natSucc => 1
+  | .natAdd | .natMul | .natPow | .natBeq | .natBle | .natBlt | .strAppend => 2
+  | .natSucc | .strLength => 1
 
 def PrimConstOp.reducible : PrimConstOp → Bool
-  | .natAdd | .natMul | .natPow | .natBeq | .natBlt | .natBle => true | .natSucc => false
+  | .natAdd | .natMul | .natPow | .natBeq | .natBlt | .natBle
+  | .strAppend | .strLength => true
+  | .natSucc => false
 
 instance : ToString PrimConst where toString
   | .nat         => "Nat"
@@ -243,5 +275,7 @@ instance : ToString PrimConst where toString
   | .op .natBle  => "Nat.ble"
   | .op .natBlt  => "Nat.blt"
   | .op .natSucc => "Nat.succ"
+  | .op .strAppend => "String.append"
+  | .op .strLength => "String.length"
 
 end Yatima.Typechecker
@@ -25,11 +25,17 @@ Note: Generally the values are assumed to already have the same type in the func
 
 namespace Yatima.Typechecker
 
+/--
+Evaluates the codomain `img` of a `pi` type, closed by `imgEnv`, at `arg`. The
+`pi` is instantiated directly on values, without being read back as an expression
+-/
+def instantiatePi (img : TypedExpr) (imgEnv : Env) (arg : SusValue) : TypecheckM Value :=
+  withNewExtendedEnv imgEnv arg $ eval img
+
 /-- Reduces the application of a `pi` type to its arguments -/
 def applyType : Value → List SusValue → TypecheckM Value
   | .pi _ img imgCtx, arg :: args => do
-    let res ← withEnv (imgCtx.extendWith arg) (eval img)
-    applyType res args
+    applyType (← instantiatePi img imgCtx arg) args
   | type, [] => pure type
   | _, _ => throw "Invalid case for applyType"
 
@@ -61,8 +67,12 @@ mutual
 
   It is assumed here that the values are typechecked, have both the same type and their
   original unevaluated terms both lived in the same context.
+
+  Each nested comparison consumes a unit of `TypecheckCtx.equalFuel`, so checks that
+  would never finish fail with an error instead. The fuel only bounds the recursion
+  of `equal` itself: evaluating the values being compared doesn't consume it.
   -/
-  partial def equal (lvl : Nat) (term term' : SusValue) : TypecheckM Bool :=
+  partial def equal (lvl : Nat) (term term' : SusValue) : TypecheckM Bool := withEqualFuel do
     match term.info, term'.info with
     | .unit, .unit => pure true
     | .proof, .proof => pure true
@@ -73,17 +83,15 @@ mutual
       | .lit lit, .lit lit' => pure $ lit == lit'
       | .sort u, .sort u' => pure $ u.equalUniv u'
       | .pi dom img env, .pi dom' img' env' => do
-        let res  ← equal lvl dom dom'
+        if !(← equal lvl dom dom') then return false
         let img  := suspend img  { ← read with env := env.extendWith  (mkSusVar dom.info  lvl) } (← get)
         let img' := suspend img' { ← read with env := env'.extendWith (mkSusVar dom'.info lvl) } (← get)
-        let res' ← equal (lvl + 1) img img'
-        pure $ res && res'
+        equal (lvl + 1) img img'
       | .lam dom bod env, .lam dom' bod' env' => do
-        let res  ← equal lvl dom dom'
+        if !(← equal lvl dom dom') then return false
         let bod  := suspend bod  { ← read with env := env.extendWith  (mkSusVar dom.info  lvl) } (← get)
         let bod' := suspend bod' { ← read with env := env'.extendWith (mkSusVar dom'.info lvl) } (← get)
-        let res' ← equal (lvl + 1) bod bod'
-        pure $ res && res'
+        equal (lvl + 1) bod bod'
       | .lam dom bod env, .app neu' args' infos' => do
         let var := mkSusVar dom.info lvl
         let bod  := suspend bod { ← read with env := env.extendWith var } (← get)
@@ -111,9 +119,8 @@ mutual
         tryEtaStruct lvl term' term
       | .app (.proj ind idx val) args _, .app (.proj ind' idx' val') args' _ =>
           if ind == ind' && idx == idx' then do
-            let eqVal ← equal lvl val.sus val'.sus
-            let eqThunks ← equalThunks lvl args args'
-            pure (eqVal && eqThunks)
+            if !(← equal lvl val.sus val'.sus) then return false
+            equalThunks lvl args args'
           else pure false
       | .exception e, _ | _, .exception e =>
         throw s!"exception in equal: {e}"
@@ -123,15 +130,13 @@ mutual
 /--
 Checks if two list of thunks `vals vals' : List SusValue` are equal by evaluating the thunks
 and checking the evaluated images are equal.
+
+Thunks are forced only as needed: lists of different lengths are unequal without forcing
+anything, and the comparison stops at the first pair of thunks that differ.
 -/
   partial def equalThunks (lvl : Nat) (vals vals' : List SusValue) : TypecheckM Bool :=
-    match vals, vals' with
-    | val::vals, val'::vals' => do
-      let eq ← equal lvl val val'
-      let eq' ← equalThunks lvl vals vals'
-      pure $ eq && eq'
-    | [], [] => pure true
-    | _, _ => pure false
+    if vals.length != vals'.length then pure false
+    else (vals.zip vals').allM fun (val, val') => equal lvl val val'
 
 end
 
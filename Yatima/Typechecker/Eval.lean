@@ -30,13 +30,15 @@ namespace Typechecker
 
 /--
 Looks for a constant by its hash `f : F` in a store and
-returns it if found. Panics otherwise.
+returns it if found. Panics otherwise, reporting the missing hash.
 
 In the code generator, this function has to be overwritten with `(open f)`,
 ignoring the second argument.
 -/
 def derefConst (f : F) (store : Store) : Const :=
-  store.find! f
+  match store.find? f with
+  | some const => const
+  | none => panic! s!"Constant {f} not found in the store"
 
 /-- TODO document. This function is overwritten btw -/
 def mkInductiveProjF (block : F) (idx : Nat) (quick : Bool) : F :=
@@ -125,17 +127,13 @@ def levels : Const → TypecheckM Nat
   | .definitionProj  p => do pure (← getDefFromProj  p).lvls
   | _ => throw "Can't retrieve universe levels of mutual blocks"
 
-def type : Const → TypecheckM Expr
-  | .axiom      x
-  | .theorem    x
-  | .opaque     x
-  | .definition x
-  | .quotient   x => pure x.type
-  | .inductiveProj   p => do pure (← getIndFromProj  p).type
-  | .constructorProj p => do pure (← getCtorFromProj p).type
-  | .recursorProj    p => do pure (← getRecrFromProj p).type
-  | .definitionProj  p => do pure (← getDefFromProj  p).type
-  | _ => throw "Can't retrieve type of mutual blocks"
+def type (c : Const) : TypecheckM Expr := match c.type?, c with
+  | some type, _ => pure type
+  | none, .inductiveProj   p => do pure (← getIndFromProj  p).type
+  | none, .constructorProj p => do pure (← getCtorFromProj p).type
+  | none, .recursorProj    p => do pure (← getRecrFromProj p).type
+  | none, .definitionProj  p => do pure (← getDefFromProj  p).type
+  | none, _ => throw "Can't retrieve type of mutual blocks"
 
 end Const
 
@@ -178,6 +176,10 @@ mutual
       let const_univs := const_univs.map (Univ.instBulkReduce env.univs)
       evalConst f const_univs
     | .letE _ val bod => do
+      -- `let`s are always inlined: values have no `let` form, so the bound value
+      -- is suspended and substituted for the variable. Only the `let`s that are
+      -- under binders of a closure survive reading back, since `quoteExpr`
+      -- reconstructs them syntactically
       let thunk := suspend val (← read) (← get)
       withExtendedEnv thunk (eval bod)
     | .pi dom img => do
@@ -212,16 +214,18 @@ mutual
     let value ← eval t
     pure ⟨reducedInfo, value⟩
 
+  /--
+  Evaluates a constant by unfolding it, if `TypedConst.unfold?` allows it.
+  Constants that don't unfold remain neutral, even when applied, unless
+  `applyConst` reduces them
+  -/
   partial def evalConst' (f : F) (univs : List Univ) : TypecheckM Value := do
     match derefConst f (← read).store with
     | .theorem _
     | .definition _ =>
-      match ← derefTypedConst f with
-      | .theorem _ deref => withEnv ⟨[], univs⟩ $ eval deref
-      | .definition _ deref part =>
-        if part then pure $ mkConst f univs
-        else withEnv ⟨[], univs⟩ $ eval deref
-      | _ => throw "Invalid const kind for evaluation"
+      match (← derefTypedConst f).unfold? with
+      | some deref => withEnv ⟨[], univs⟩ $ eval deref
+      | none => pure $ mkConst f univs
     | _ => pure $ mkConst f univs
 
   /-- Evaluates the `Yatima.Const` that's referenced by a constant index -/
@@ -264,7 +268,8 @@ mutual
     -- hold the info of the sub stuck application `h a1 .. an` for quoting to be done correctly
     | .app neu args infos => pure $ .app neu (arg :: args) (val.info :: infos)
     -- Since terms are well-typed we know that any other case is impossible
-    | _ => throw "Invalid case for apply"
+    | .exception e => throw e
+    | val => do throw s!"Invalid case for apply: {val.ctorName} {← ppValue val} isn't a function"
 
   /--
   Applies a named constant, referred by its constant index `f : F` to the list of arguments
@@ -293,9 +298,8 @@ mutual
     -- Assumes a partial application of f to args, which means in particular,
     -- that it is in normal form
     else match ← derefTypedConst f with
-    | .recursor _ params motives minors indices isK indProj rules =>
-      let majorIdx := params + motives + minors + indices
-      if args.length != majorIdx then
+    | tconst@(.recursor _ params motives _ indices isK internal indProj rules) =>
+      if tconst.majorIdx? != some args.length then
         pure $ .app (.const f univs) (arg :: args) (info :: infos)
       else if isK then
         -- sanity check
@@ -309,15 +313,33 @@ mutual
       else
         let params := args.take params
         match ← toCtorIfLitOrStruct indProj params univs arg with
-        | .app (Neutral.const f _) args' _ => match ← derefTypedConst f with
-          | .constructor _ idx _ =>
-            match rules.get? idx with
+        | .app (Neutral.const ctorF _) args' _ => match ← derefTypedConst ctorF with
+          | .constructor _ cidx ctorParams _ =>
+            -- The recursion rule of an internal recursor is found by the identity of
+            -- the constructor, that is, its block and its position in it, rather than
+            -- by its index alone, which could coincide with the index of a constructor
+            -- of another inductive. External recursors, the auxiliary recursors of
+            -- nested inductives, have rules for the constructors of the inductive that
+            -- is nested (such as `List`), which lives in another block
+            if internal then
+              let .constructorProj ⟨block, idx, _⟩ := derefConst ctorF (← read).store
+                | throw s!"Constructor {ctorF} is not a constructor projection"
+              if block != indProj.block || idx != indProj.idx then
+                return .app (Neutral.const f univs) (arg :: args) (info :: infos)
+            match rules.get? cidx with
             | some (fields, rhs) =>
+              -- The major premise must be a saturated constructor application, that
+              -- is, its parameters followed by exactly `fields` fields. Since `args'`
+              -- holds the last argument first, taking `fields` arguments out of an
+              -- over (or under) applied constructor would pick the wrong ones
+              let nCtorArgs := ctorParams + fields
+              if args'.length != nCtorArgs then
+                throw s!"Constructor {ctorF} has {args'.length} arguments as a major premise, but {nCtorArgs} were expected"
               let exprs := (args'.take fields) ++ (args.drop indices)
               withEnv ⟨exprs, univs⟩ $ eval rhs.toImplicitLambda
             -- Since we assume expressions are previously type checked, we know that this constructor
             -- must have an associated recursion rule
-            | none => throw s!"Constructor {f} has no associated recursion rule"
+            | none => throw s!"Constructor {ctorF} has no associated recursion rule"
           | _ => pure $ .app (Neutral.const f univs) (arg :: args) (info :: infos)
         | _ => pure $ .app (Neutral.const f univs) (arg :: args) (info :: infos)
     | .quotient _ kind => match kind with
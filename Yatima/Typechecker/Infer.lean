@@ -58,6 +58,27 @@ def infoFromType (typ : SusValue) : TypecheckM TypeInfo :=
     | .sort lvl => pure (.sort lvl)
     | _ => pure .none
 
+/--
+The type that the quotient constant of the given kind must have, which refers
+to `Quot`, `Quot.mk` and `Eq` by their hashes in the store
+-/
+def quotKindType : QuotKind → TypecheckM IR.Expr
+  | .type => pure Quotient.quotType
+  | .ctor => return Quotient.mkType (← quotF .type)
+  | .lift => return Quotient.liftType (← quotF .type) (← eqF)
+  | .ind  => return Quotient.indType (← quotF .type) (← quotF .ctor)
+where
+  quotF (kind : QuotKind) : TypecheckM F := do
+    let some (f, _) := (← read).store.toList.find? fun (_, const) => match const with
+        | .quotient data => data.kind == kind
+        | _ => false
+      | throw s!"Quotient constant of kind {repr kind} not found in the store"
+    pure f
+  eqF : TypecheckM F := do
+    let some (f, _) := (← read).constNames.toList.find? (·.2 == ``Eq)
+      | throw "`Eq` not found in the store"
+    pure f
+
 mutual
 
   partial def getStructInfo (v : Value) :
@@ -73,7 +94,7 @@ mutual
       withLimitedAxioms $ checkConst indF
       let ctorF := mkConstructorProjF p.block p.idx 0 (← read).quick
       match (← get).typedConsts.find? ctorF with
-      | .some (.constructor type _ _) =>
+      | .some (.constructor type ..) =>
         return (indF, type, univs, params)
       | _ => throw s!"Implementation broken: ctorF {ctorF} is not a constructor"
     | v => throw s!"Expected a structure type, found {← ppValue v}"
@@ -86,7 +107,11 @@ mutual
     if !(← eqSortInfo inferType type) then
       throw s!"Term: {← ppTypedExpr term}\nInfo mismatch:\n{repr inferType.info}\n\nnot equal to\n{repr type.info}\n\nExpected type: {← ppValue type.get}\nInferred type: {← ppValue inferType.get}"
     if !(← equal (← read).lvl type inferType) then
-      throw s!"Expected type {← ppValue type.get}, found type {← ppValue inferType.get}"
+      let ctx ← read
+      let const := match ctx.recF? with
+        | some f => s!" while checking {ctx.constNames.getF f}"
+        | none => ""
+      throw s!"Type mismatch{const} in term {← ppTypedExpr term}\nExpected type: {← ppValue type.get}\nFound type: {← ppValue inferType.get}\nIn {← ppTypecheckCtx (force := true)}"
     pure term
 
   /-- Infers the type of `term : IR.Expr`. Returns the typed IR for `term` along with its inferred type  -/
@@ -110,6 +135,11 @@ mutual
         | some (constF, typeValFn) =>
           if some constF == ctx.recF? then
             throw s!"Invalid recursion in {(← read).constNames.getF constF}"
+          -- references between the members of a definition block must use the
+          -- universes they were declared with. Inductive blocks are exempt, since
+          -- their recursors refer to the inductives with other universes
+          if ctx.recF?.isSome && lvls != ctx.env.univs then
+            throw s!"Universe polymorphic recursion in {ctx.constNames.getF constF}"
           let type := typeValFn lvls
           let term := ⟨← infoFromType type, .const constF lvls⟩
           pure (term, type)
@@ -192,7 +222,7 @@ mutual
         | .pi dom img piEnv =>
           let info ← infoFromType dom
           let proj := suspend ⟨info, .proj indF i expr⟩ (← read) (← get)
-          ctorType ← withNewExtendedEnv piEnv proj $ eval img
+          ctorType ← instantiatePi img piEnv proj
         | _ => pure ()
       match ctorType with
       | .pi dom _ _  =>
@@ -218,6 +248,25 @@ mutual
       pure (expr, u)
     | val => throw s!"Expected a sort type, found {← ppValue val}"
 
+  /--
+  Whether a constructor of a proposition allows eliminating it into any sort:
+  each of its fields must either be a proof or appear among the indices of the
+  type that the constructor returns, so that it's determined by the major premise
+  -/
+  partial def ctorAllowsLargeElim (params : Nat) (type : IR.Expr) (depth : Nat := 0)
+      (dataFields : List Nat := []) : TypecheckM Bool := do
+    match type with
+    | .pi dom img =>
+      let (dom, lvl) ← isSort dom
+      let dataFields := if depth ≥ params && lvl != .zero then depth :: dataFields else dataFields
+      let ctx ← read
+      let domVal := suspend dom ctx (← get)
+      withExtendedCtx (mkSusVar (← infoFromType domVal) ctx.lvl) domVal $
+        ctorAllowsLargeElim params img (depth + 1) dataFields
+    | _ =>
+      let indices := type.appArgs.drop params
+      pure $ dataFields.all fun field => indices.contains (.var (depth - 1 - field) [])
+
   partial def checkIndBlock (indBlockF : F) : TypecheckM Unit := do
     let quick := (← read).quick
     let indBlock ← match derefConst indBlockF (← read).store with
@@ -247,7 +296,7 @@ mutual
         let stt ← get
         let typeSus := (suspend type {ctx with env := .mk ctx.env.exprs ·} stt)
         mutTypes := mutTypes.insert (start + cidx) (f, typeSus)
-        modify fun stt => { stt with typedConsts := stt.typedConsts.insert f (.constructor type ctor.idx ctor.fields) }
+        modify fun stt => { stt with typedConsts := stt.typedConsts.insert f (.constructor type ctor.idx ctor.params ctor.fields) }
 
     -- Check all recursor types
     for (indIdx, ind) in indBlock.enum do
@@ -269,10 +318,36 @@ mutual
         let univs := List.range recr.lvls |>.map .var
         let (type, _) ← withEnv ⟨ [], univs ⟩ $ withMutTypes mutTypes $ isSort recr.type
         let indProj := ⟨indBlockF, indIdx⟩
+        -- Internal recursors must agree with the inductive they eliminate: they have
+        -- one rule per constructor, in order, and each rule binds the constructor's fields
+        if recr.internal then
+          let name := (← read).constNames.getF f
+          if recr.params != ind.params then
+            throw s!"Recursor {name} has {recr.params} parameters, but its inductive has {ind.params}"
+          if recr.indices != ind.indices then
+            throw s!"Recursor {name} has {recr.indices} indices, but its inductive has {ind.indices}"
+          if recr.rules.length != ind.ctors.length then
+            throw s!"Recursor {name} has {recr.rules.length} rules, but its inductive has {ind.ctors.length} constructors"
+          for (cidx, ctor) in ind.ctors.enum, rule in recr.rules do
+            if rule.fields != ctor.fields then
+              throw s!"Rule {cidx} of recursor {name} has {rule.fields} fields, but its constructor has {ctor.fields}"
+          -- Recursors of propositions can only eliminate into sorts other than `Prop`
+          -- when the proposition is a subsingleton, as in Lean: it isn't defined in a
+          -- mutual block and it has no constructors, or a single one whose fields
+          -- are determined by the major premise
+          if ind.isProp && recr.motiveSort? != some .zero then
+            let subsingleton ← match indBlock, ind.ctors with
+              | [_], [] => pure true
+              | [_], [ctor] =>
+                withEnv ⟨ [], List.range ctor.lvls |>.map .var ⟩ $ withMutTypes mutTypes $
+                  ctorAllowsLargeElim ctor.params ctor.type
+              | _, _ => pure false
+            unless subsingleton do
+              throw s!"Recursor {name} eliminates a proposition that isn't a subsingleton into any sort"
         let rules ← recr.rules.mapM fun rule => do
           let (rhs, _) ← withEnv ⟨ [], univs ⟩ $ withMutTypes mutTypes $ infer rule.rhs
           pure (rule.fields, rhs)
-        let recrConst := .recursor type recr.params recr.motives recr.minors recr.indices recr.isK indProj ⟨rules⟩
+        let recrConst := .recursor type recr.params recr.motives recr.minors recr.indices recr.isK recr.internal indProj ⟨rules⟩
         modify fun stt => { stt with typedConsts := stt.typedConsts.insert f recrConst }
 
     return ()
@@ -284,10 +359,10 @@ mutual
   only has to check the other `Const` constructors.
   -/
   partial def checkConst (f : F) : TypecheckM Unit := withResetCtx do
-    match (← get).typedConsts.find? f with
-    | some _ =>
+    match ← isChecked f with
+    | true =>
       pure ()
-    | none =>
+    | false =>
       let c := derefConst f (← read).store
       if c.isMutType then return ()
       let univs := List.range (← c.levels) |>.map .var
@@ -356,6 +431,8 @@ mutual
             checkIndBlock indBlockF
             return ()
           | .quotient data =>
+            if data.type != (← quotKindType data.kind) then
+              throw s!"{(← read).constNames.getF f} doesn't have the type of its quotient kind"
             let (type, _) ← isSort (← c.type)
             pure $ .quotient type data.kind
           | _ => throw "Impossible case. Cannot typecheck a mutual block."
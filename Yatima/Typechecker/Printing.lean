@@ -1,4 +1,5 @@
 import Yatima.Typechecker.TypecheckM
+import Yatima.Common.TopoSort
 import Lean.PrettyPrinter
 
 /-!
@@ -11,11 +12,17 @@ the typechecker.
 open Lean
 
 open Yatima.Typechecker in
+/--
+The name of the constant of hash `f`, for printing. Unnamed constants are shown
+by a prefix of their hash, marked with `#`
+-/
 def Yatima.Typechecker.ConstNames.getF
     (constNames : ConstNames) (f : Lurk.F) : Format :=
   match constNames.find? f with
   | some name => toString name
-  | none => toString f
+  | none =>
+    let hash := toString f
+    if hash.length > 12 then s!"#{hash.take 12}…" else s!"#{hash}"
 
 namespace Yatima.IR
 open Yatima.Typechecker
@@ -62,6 +69,10 @@ instance : ToFormat BinderInfo where format
   | .strictImplicit => "strict"
   | .instImplicit   => "inst"
 
+instance : ToFormat Literal where format
+  | .natVal num => format num
+  | .strVal str => str.quote
+
 instance : ToFormat QuotKind where format
   | .type => "Quot"
   | .ctor => "Quot.mk"
@@ -87,16 +98,26 @@ mutual
     | .succ u => ppSuccUniv (acc + 1) u
     | u => s!"{acc}+{ppUniv u}"
 
-  partial def ppUnivs (us : List Univ) : Format :=
-    bracket "{" (joinSep (us.map ppUniv) ", ") "}"
+  /--
+  Prints a sort, with `Sort 0` as `Prop` so that it's atomic like `Expr.isAtom`
+  and `TypedExpr.isAtom` assume
+  -/
+  partial def ppSort : Univ → Format
+    | .zero => "Prop"
+    | u => f!"Sort {ppUniv u}"
+
+  /-- Prints universe arguments Lean-style, as `.{u, v}`, and nothing if there are none -/
+  partial def ppUnivs : List Univ → Format
+    | [] => .nil
+    | us => ".{" ++ joinSep (us.map ppUniv) ", " ++ "}"
 
   partial def ppExpr (e : Expr) : TypecheckM Format := do
     let constNames := (← read).constNames
     match e with
-    | .var name us => return f!"v_{name}@{ppUnivs us}"
-    | .sort u => return f!"Sort {ppUniv u}"
+    | .var name us => return f!"v_{name}{ppUnivs us}"
+    | .sort u => return ppSort u
     | .const name us =>
-      return f!"{constNames.getF name}@{ppUnivs us}"
+      return f!"{constNames.getF name}{ppUnivs us}"
     | .app func body => match func with
       | .app .. => return f!"{← ppExpr func} {← paren body}"
       | _ => return f!"{← paren func} {← paren body}"
@@ -107,10 +128,8 @@ mutual
     | .letE type value body =>
       return f!"let _ : {← ppExpr type} := {← ppExpr value}"
         ++ ";" ++ .line ++ f!"{← ppExpr body}"
-    | .lit lit => match lit with
-      | .natVal num => return f!"{num}"
-      | .strVal str => return f!"\"{str}\""
-    | .proj idx expr => return f!"{← paren expr}.{idx})"
+    | .lit lit => return format lit
+    | .proj idx expr => return f!"{← paren expr}.{idx}"
 end
 
 partial def ppDefinition (defn : Definition) : TypecheckM Format :=
@@ -164,6 +183,49 @@ partial def ppConst (const : Const) : TypecheckM Format :=
   | .mutIndBlock block =>
     return f!"{← prefixJoin ("\n" ++ .line) (Array.mk block) ppInductive}"
 
+/-- Prints the member of a mutual block that a projection points to -/
+partial def ppProjMember (const : Const) : TypecheckM Format := do
+  let store := (← read).store
+  match const with
+  | .inductiveProj ⟨block, idx⟩ => match store.find? block with
+    | some (.mutIndBlock inds) => match inds.get? idx with
+      | some ind => ppInductive ind
+      | none => ppConst const
+    | _ => ppConst const
+  | .constructorProj ⟨block, idx, cidx⟩ => match store.find? block with
+    | some (.mutIndBlock inds) => match inds.get? idx >>= (·.ctors.get? cidx) with
+      | some ctor => ppConstructor ctor
+      | none => ppConst const
+    | _ => ppConst const
+  | .recursorProj ⟨block, idx, ridx⟩ => match store.find? block with
+    | some (.mutIndBlock inds) => match inds.get? idx >>= (·.recrs.get? ridx) with
+      | some recr => ppRecursor recr
+      | none => ppConst const
+    | _ => ppConst const
+  | .definitionProj ⟨block, idx⟩ => match store.find? block with
+    | some (.mutDefBlock defs) => match defs.get? idx with
+      | some defn => ppDefinition defn
+      | none => ppConst const
+    | _ => ppConst const
+  | _ => ppConst const
+
+/--
+Prints every named constant of the store, each one after the constants that it
+depends on
+-/
+def ppStore : TypecheckM Format := do
+  let ctx ← read
+  let roots := ctx.constNames.foldl (init := []) fun acc f _ => f :: acc
+  let deps f := ctx.store.find? f |>.map (·.deps.toList) |>.getD []
+  let order ← match topoSort roots deps with
+    | .ok order => pure order
+    | .error err => throw err
+  let mut result := .nil
+  for f in order do
+    let (some name, some const) := (ctx.constNames.find? f, ctx.store.find? f) | continue
+    result := result ++ f!"-- {name}" ++ .line ++ (← ppProjMember const) ++ .line ++ .line
+  return result
+
 end Yatima.IR.PP
 
 namespace Yatima.Typechecker
@@ -194,9 +256,9 @@ mutual
   /-- Printer of expressions -/
   partial def ppTypedExpr (t : TypedExpr) : TypecheckM Format := match t.expr with
     | .var idx => return f!"v_{idx}"
-    | .sort u => return f!"Sort {ppUniv u}"
+    | .sort u => return ppSort u
     | .const k univs =>
-      return f!"{(← read).constNames.getF k}@{ppUnivs univs}"
+      return f!"{(← read).constNames.getF k}{ppUnivs univs}"
     | .app fnc arg => match fnc.expr with
       | .app .. => return f!"{← ppTypedExpr fnc} {← paren arg}"
       | _ => return f!"{← paren fnc} {← paren arg}"
@@ -205,9 +267,8 @@ mutual
     | .pi dom cod =>
       return f!"(_: {← ppTypedExpr dom}) → {← ppTypedExpr cod}"
     | .letE typ val bod => return f!"let _ : {← ppTypedExpr typ} := {← ppTypedExpr val} in {← ppTypedExpr bod}"
-    | .lit (.natVal x) => return f!"{x}"
-    | .lit (.strVal x) => return f!"\"{x}\""
-    | .proj _ idx val => return f!"{← ppTypedExpr val}.{idx}"
+    | .lit lit => return format lit
+    | .proj _ idx val => return f!"{← paren val}.{idx}"
 
 end
 
@@ -224,8 +285,8 @@ mutual
       match env.exprs.get? idx with
      | some val => ppValue val.get
      | none => return f!"!_@{idx}!"
-    | .sort u => return f!"Sort {ppUniv u}"
-    | .const k univs => return f!"{(← read).constNames.getF k}@{ppUnivs univs}"
+    | .sort u => return ppSort u
+    | .const k univs => return f!"{(← read).constNames.getF k}{ppUnivs univs}"
     | .app fnc arg => match fnc.expr with
       | .app .. => return f!"{← ppTypedExprWith fnc env} {← parenWith arg env}"
       | _ => return f!"{← parenWith fnc env} {← parenWith arg env}"
@@ -235,30 +296,34 @@ mutual
     | .pi dom cod =>
       return f!"(_ : {← ppTypedExprWith dom env}) → {← ppTypedExprWith cod env}"
     | .letE typ val bod => return f!"let _ : {← ppTypedExprWith typ env} := {← ppTypedExprWith val env} in {← ppTypedExprWith bod env}"
-    | .lit (.natVal x) => return f!"{x}"
-    | .lit (.strVal x) => return f!"\"{x}\""
-    | .proj _ idx val => return f!"{← ppTypedExprWith val env}.{idx}"
+    | .lit lit => return format lit
+    | .proj _ idx val => return f!"{← parenWith val env}.{idx}"
 
   private partial def ppNeutral (neu : Neutral) : TypecheckM Format := match neu with
     | .fvar idx .. => return f!"fv_{idx}"
-    | .const k univs => return f!"{(← read).constNames.getF k}@{ppUnivs univs}"
-    | .proj _ idx val => return f!"{← ppValue val.value}.{idx}"
+    | .const k univs => return f!"{(← read).constNames.getF k}{ppUnivs univs}"
+    | .proj _ idx val => return f!"{← parenValue val.value}.{idx}"
+
+  /-- Prints a value in parentheses, unless it's atomic -/
+  private partial def parenValue (val : Value) : TypecheckM Format :=
+    match val with
+    | .app _ [] _ | .lit _ | .sort .zero => ppValue val
+    | _ => return f!"({← ppValue val})"
 
   /-- Auxiliary function to print a chain of unevaluated applications as a single application -/
   private partial def ppSpine (neu : Neutral) (args : Args) : TypecheckM Format := do
-    List.foldrM (fun arg str => return f!"{str} {← ppValue arg.get}") (← ppNeutral neu) args
+    List.foldrM (fun arg str => return f!"{str} {← parenValue arg.get}") (← ppNeutral neu) args
 
   /-- Printer of typechecker values -/
   partial def ppValue (val : Value) : TypecheckM Format :=
     match val with
-    | .sort u => return f!"Sort {ppUniv u}"
+    | .sort u => return ppSort u
     | .app neu args _ => ppSpine neu args
     | .lam dom bod ctx =>
       return f!"fun (_ : {← ppValue dom.get}) =>{indentD (← ppTypedExprWith bod ctx)}"
     | .pi dom cod ctx =>
       return f!"(_ : {← ppValue dom.get}) → {← ppTypedExprWith cod ctx}"
-    | .lit (.natVal x) => return f!"{x}"
-    | .lit (.strVal x) => return f!"\"{x}\""
+    | .lit lit => return format lit
     | .exception e => return f!"exception {e}"
 end
 
@@ -267,14 +332,37 @@ end
 -- instance : ToFormat Value where format := ppValue
 -- instance : ToString Value where toString := pretty ∘ ppValue
 
-def ppTypecheckCtx : TypecheckM Format := do
-  let ⟨lvl, env, types, _, _, _, _, _, _, _⟩ ← read
+def ppTypeInfo : TypeInfo → Format
+  | .unit => "unit"
+  | .proof => "proof"
+  | .none => "none"
+  | .sort u => f!"sort {ppUniv u}"
+
+/--
+Printer of suspended values that doesn't force them, so it's safe to use on
+values whose evaluation would fail or not terminate. Only their type info is shown
+-/
+def ppSusValue (val : SusValue) : Format :=
+  f!"<suspended {ppTypeInfo val.info}>"
+
+/--
+Printer of the typechecker context. The values in it are only evaluated and
+printed when `force` is set, and are shown as suspended otherwise. Values whose
+evaluation fails are shown as suspended either way
+-/
+def ppTypecheckCtx (force : Bool := false) : TypecheckM Format := do
+  let ⟨lvl, env, types, _, _, _, _, _, _, _, _⟩ ← read
+  let ppSus (val : SusValue) : TypecheckM Format :=
+    if force then
+      match val.get with
+      | .exception _ => pure $ ppSusValue val
+      | v => tryCatch (ppValue v) fun _ => pure $ ppSusValue val
+    else pure $ ppSusValue val
   let env := ← match env with
     | .mk vals us => do
-      let vals : List Value := vals.map (·.get)
-      let fields := f!"vals := {← vals.mapM ppValue}" ++ line ++ f!"us := {us.map ppUniv}"
+      let fields := f!"vals := {← vals.mapM ppSus}" ++ line ++ f!"us := {us.map ppUniv}"
       return f!"env with{indentD fields}"
-  let types ← types.mapM fun t => ppValue t.get
+  let types ← types.mapM ppSus
   let fields := f!"lvl := {lvl}" ++ line ++ f!"env := {env}" ++ line ++ f!"types := {types}"
   return f!"typecheckCtx with{indentD fields}"
 
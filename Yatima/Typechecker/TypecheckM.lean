@@ -38,6 +38,11 @@ structure TypecheckCtx where
   recF?       : Option F
   quick       : Bool
   dbg         : Bool := false
+  /-- How much deeper definitional equality checks can still recurse. Bounds
+    comparisons of values that would only be equal after infinitely many steps,
+    but not the evaluation of those values, so unfolding a definition that
+    never reaches a normal form isn't bounded by it -/
+  equalFuel   : Nat := 10000
   deriving Inhabited
 
 /--
@@ -69,10 +74,26 @@ def TypecheckM.run (ctx : TypecheckCtx) (stt : TypecheckState) (m : TypecheckM 
   | .error e => .error e
   | .ok (a, _) => .ok a
 
+/--
+Whether the constant of hash `f` has already been typechecked. Since hashes
+commit to the whole constant, a constant only needs to be checked once
+-/
+def isChecked (f : F) : TypecheckM Bool :=
+  return (← get).typedConsts.contains f
+
 /-- Evaluates a `TypecheckM` computation with an `TypecheckCtx` whose environment is fixed by `env` -/
 def withEnv (env : Env) : TypecheckM α → TypecheckM α :=
   withReader fun ctx => { ctx with env := env }
 
+/--
+Evaluates a `TypecheckM` computation with one less unit of `equalFuel`, and
+throws an error if it has run out
+-/
+def withEqualFuel (x : TypecheckM α) : TypecheckM α := do
+  match (← read).equalFuel with
+  | 0 => throw "Definitional equality check exceeded its depth limit"
+  | fuel + 1 => withReader (fun ctx => { ctx with equalFuel := fuel }) x
+
 /--
 Evaluates a `TypecheckM` computation with a reset `TypecheckCtx`.
 -/
@@ -146,6 +167,7 @@ def primToF : PrimConst → Option F
   | .boolFalse => return .ofNat 0x243ab3653bc8479149b60ec2147ff4a5fa036805561c68d485b1422205986966
   | .op .natSucc => return .ofNat 0x3fef544888eb8d71789a4b163c8e8c41bdc37dc835d861b0876c9c66a0c30519
   | .op .natAdd => return .ofNat 0x1dba42b440d4fd17ef127207171ef74d16fac5741f5cddd3e4eae15ff5f3299e
+  | _ => none
 def fToPrim : F → Option PrimConst
   | .ofNat 0x1d1157688c2c860089bd47e1fd290d32cb6ad280c3fdee2f6071df2efce99077 => return .op .natBlt
   | .ofNat 0x2d9f468676f4b73642a4ef82ee4084925820f865dedcc57db32130861ad54b81 => return .op .natBle
@@ -175,6 +197,7 @@ def primToFQuick : PrimConst → Option F
   | .boolFalse => return .ofNat 16195091492847522412
   | .op .natSucc => return .ofNat 6836287016865057964
   | .op .natAdd => return .ofNat 14029550093476971811
+  | _ => none
 def fToPrimQuick : F → Option PrimConst
   | .ofNat 4822643605371257236 => return .op .natBlt
   | .ofNat 2951728617574817879 => return .op .natBle
@@ -277,5 +300,17 @@ def PrimConstOp.toPrimOp : PrimConstOp → PrimOp
       else do
         pure $ some $ .neu (.const (← primF .boolFalse) [])
     | _, _ => pure none
+  | .strAppend => .mk fun vs => do
+    let some (v, v') := do pure (← vs.get? 0, ← vs.get? 1)
+      | throw "At least two SusValue elements needed for PrimConstOp.strAppend"
+    match v.get, v'.get with
+    | .lit (.strVal v), .lit (.strVal v') => pure $ .some $ .lit (.strVal (v ++ v'))
+    | _, _ => pure none
+  | .strLength => .mk fun vs => do
+    let some v := vs.get? 0
+      | throw "At least one SusValue element needed for PrimConstOp.strLength"
+    match v.get with
+    | .lit (.strVal v) => pure $ .some $ .lit (.natVal v.length)
+    | _ => pure none
 
 end Yatima.Typechecker
@@ -15,6 +15,47 @@ def typecheckAll (store : Store) (constNames : ConstNames) : Except String Unit
   | .ok u => .ok u
   | .error err => throw err
 
+/--
+Typechecks the constants from a store that aren't in a `previous` one, which is
+assumed to have been typechecked already. Since constants are addressed by
+hashes that commit to the hashes of their dependencies, a constant whose hash is
+in `previous` hasn't changed, and neither have its dependencies. Conversely, the
+dependents of a changed constant have new hashes as well.
+
+Unchanged constants are still checked when changed ones refer to them, since
+their typed versions aren't kept between runs. Returns the hashes of the
+changed constants
+-/
+def typecheckChanged (store previous : Store) (constNames : ConstNames) :
+    Except String (Array Lurk.F) :=
+  let changed := store.foldl (init := #[]) fun acc f _ =>
+    if previous.contains f then acc else acc.push f
+  let aux := do changed.forM checkConst; pure changed
+  match TypecheckM.run (.init store constNames true) default aux with
+  | .ok changed => .ok changed
+  | .error err => throw err
+
+/--
+Unfolds the constants `f` and `g` and checks that the resulting values are
+definitionally equal. On a mismatch, the error shows both values in weak head
+normal form. Meant for asserting how terms reduce
+-/
+def checkDefEq (store : Store) (constNames : ConstNames) (f g : Lurk.F) :
+    Except String Unit :=
+  let aux := do
+    checkConst f
+    checkConst g
+    let univs := List.range (← (derefConst f store).levels) |>.map .var
+    let val  ← evalConst f univs
+    let val' ← evalConst g univs
+    unless ← equal 0 ⟨.none, .pure val⟩ ⟨.none, .pure val'⟩ do
+      throw s!"{constNames.getF f} and {constNames.getF g} aren't definitionally equal\nReduced {constNames.getF f}: {← PP.ppValue val}\nReduced {constNames.getF g}: {← PP.ppValue val'}"
+  TypecheckM.run (.init store constNames true) default aux
+
+/-- Prints all named constants from a store, each one after its dependencies -/
+def ppStore (store : Store) (constNames : ConstNames) : Except String Std.Format :=
+  TypecheckM.run (.init store constNames true) default IR.PP.ppStore
+
 /--
 This is the function that's supposed to be transpiled to Lurk, which does
 `open f` instead of retrieving constants from a store
@@ -49,12 +49,24 @@ lean_lib Fixtures {
 lean_exe Tests.AnonGroups.Definitions      { supportInterpreter := true }
 lean_exe Tests.AnonGroups.Inductives       { supportInterpreter := true }
 lean_exe Tests.AnonGroups.ToImport         { supportInterpreter := true }
+lean_exe Tests.Cli.TypecheckCmd            { supportInterpreter := true }
+lean_exe Tests.Common.LightData            { supportInterpreter := true }
+lean_exe Tests.Common.TopoSort             { supportInterpreter := true }
+lean_exe Tests.ContAddr.ContAddrError      { supportInterpreter := true }
+lean_exe Tests.Datatypes.Const             { supportInterpreter := true }
+lean_exe Tests.Datatypes.Env               { supportInterpreter := true }
+lean_exe Tests.Datatypes.Expr              { supportInterpreter := true }
+lean_exe Tests.Datatypes.Univ              { supportInterpreter := true }
 lean_exe Tests.Termination.NastyInductives { supportInterpreter := true }
 lean_exe Tests.Termination.TrickyDef       { supportInterpreter := true }
 lean_exe Tests.Termination.Init            { supportInterpreter := true }
 lean_exe Tests.CodeGeneration.Primitives   { supportInterpreter := true }
 lean_exe Tests.CodeGeneration.TrickyTypes  { supportInterpreter := true }
 lean_exe Tests.Typechecker.Accept          { supportInterpreter := true }
+lean_exe Tests.Typechecker.Datatypes       { supportInterpreter := true }
+lean_exe Tests.Typechecker.Equal           { supportInterpreter := true }
+lean_exe Tests.Typechecker.Primitives      { supportInterpreter := true }
+lean_exe Tests.Typechecker.Printing        { supportInterpreter := true }
 lean_exe Tests.Typechecker.Reject          { supportInterpreter := true }
 lean_exe Tests.Typechecker.TypecheckInLurk { supportInterpreter := true }
 